@@ -0,0 +1,77 @@
+use std::path::PathBuf;
+
+use clap::Args;
+use reqwest::Method;
+
+#[derive(Args, Debug)]
+#[command(after_help = "Examples:\n  \
+    gleap api GET /tickets/ID/activitylogs\n  \
+    gleap api GET /tickets -q status=OPEN --paginate\n  \
+    gleap api PATCH /tickets/ID -f title='Login broken' -F 'tags=[\"billing\"]'\n  \
+    gleap api POST /tickets --input ticket.json")]
+pub struct ApiArgs {
+    /// HTTP method (GET, POST, PUT, PATCH, DELETE)
+    #[arg(value_parser = parse_method)]
+    pub method: Method,
+
+    /// Path relative to the API base URL, e.g. /tickets
+    pub path: String,
+
+    /// String body field; repeat for more. Sent as query parameters with GET
+    #[arg(short = 'f', long = "field", value_name = "KEY=VALUE", value_parser = parse_pair)]
+    pub fields: Vec<(String, String)>,
+
+    /// Typed body field: numbers, true/false, null, arrays and objects are
+    /// sent as JSON, anything else as a string
+    #[arg(short = 'F', long = "typed-field", value_name = "KEY=VALUE", value_parser = parse_pair)]
+    pub typed_fields: Vec<(String, String)>,
+
+    /// Read the JSON request body from a file ('-' for stdin)
+    #[arg(long, value_name = "FILE", conflicts_with_all = ["fields", "typed_fields"])]
+    pub input: Option<PathBuf>,
+
+    /// Query parameter; repeat for more
+    #[arg(short = 'q', long = "query", value_name = "KEY=VALUE", value_parser = parse_pair)]
+    pub query: Vec<(String, String)>,
+
+    /// Fetch every page of a list endpoint using skip/limit and print the
+    /// items as one array (a `limit` query parameter sets the page size)
+    #[arg(long)]
+    pub paginate: bool,
+}
+
+fn parse_method(value: &str) -> Result<Method, String> {
+    Method::from_bytes(value.to_ascii_uppercase().as_bytes())
+        .map_err(|_| format!("invalid HTTP method '{value}'"))
+}
+
+fn parse_pair(value: &str) -> Result<(String, String), String> {
+    match value.split_once('=') {
+        Some((key, value)) if !key.is_empty() => Ok((key.to_string(), value.to_string())),
+        _ => Err(format!("invalid '{value}': expected KEY=VALUE")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_method() {
+        assert_eq!(parse_method("patch"), Ok(Method::PATCH));
+        assert_eq!(parse_method("GET"), Ok(Method::GET));
+        assert!(parse_method("BAD METHOD").is_err());
+    }
+
+    #[test]
+    fn test_parse_pair() {
+        assert_eq!(
+            parse_pair("status=OPEN"),
+            Ok(("status".into(), "OPEN".into()))
+        );
+        assert_eq!(parse_pair("q=a=b"), Ok(("q".into(), "a=b".into())));
+        assert_eq!(parse_pair("empty="), Ok(("empty".into(), String::new())));
+        assert!(parse_pair("=x").is_err());
+        assert!(parse_pair("novalue").is_err());
+    }
+}
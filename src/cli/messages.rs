@@ -1,18 +1,15 @@
-use clap::Subcommand;
+use clap::{Args, Subcommand};
 
-use super::shared::Pagination;
+use gleap::models::message::MessageType;
+use gleap::models::ticket::FilterValue;
+
+use super::shared::{Pagination, timestamp};
+use super::tickets::FilterParser;
 
 #[derive(Subcommand, Debug)]
 pub enum MessagesAction {
-    /// List messages for a ticket
-    List {
-        /// Ticket ID to list messages for
-        #[arg(long)]
-        ticket: String,
-
-        #[command(flatten)]
-        pagination: Pagination,
-    },
+    /// List messages, for one ticket or across the project
+    List(ListArgs),
 
     /// Add an internal note to a ticket
     Note {
@@ -20,8 +17,11 @@ pub enum MessagesAction {
         #[arg(long)]
         ticket: String,
 
-        /// Note text
+        /// Note text (Markdown is converted to rich text)
         text: String,
+
+        #[command(flatten)]
+        text_format: TextFormat,
     },
 
     /// Add a comment reply to a ticket
@@ -30,7 +30,58 @@ pub enum MessagesAction {
         #[arg(long)]
         ticket: String,
 
-        /// Comment text
+        /// Comment text (Markdown is converted to rich text)
         text: String,
+
+        #[command(flatten)]
+        text_format: TextFormat,
     },
 }
+
+#[derive(Args, Debug)]
+pub struct ListArgs {
+    /// Only messages on this ticket
+    #[arg(long)]
+    pub ticket: Option<String>,
+
+    /// Filter by message type; repeat or comma-separate to match any
+    /// (raw:VALUE sends a value not listed here)
+    #[arg(long = "type", value_delimiter = ',', ignore_case = true,
+          value_parser = FilterParser::<MessageType>::new())]
+    pub message_type: Vec<FilterValue<MessageType>>,
+
+    /// Only bot-authored messages
+    #[arg(long, overrides_with = "no_bot")]
+    pub bot: bool,
+
+    /// Exclude bot-authored messages
+    #[arg(long, overrides_with = "bot")]
+    pub no_bot: bool,
+
+    /// Only messages created at or after this time (YYYY-MM-DD, RFC 3339, or
+    /// relative like 30m, 12h, 7d, 2w)
+    #[arg(long, value_parser = timestamp)]
+    pub since: Option<String>,
+
+    /// Only messages created at or before this time (same formats as --since)
+    #[arg(long, value_parser = timestamp)]
+    pub until: Option<String>,
+
+    /// Translate message content into this language (e.g. en, de)
+    #[arg(long, value_name = "LANG")]
+    pub translate: Option<String>,
+
+    #[command(flatten)]
+    pub pagination: Pagination,
+}
+
+#[derive(Args, Debug, Clone)]
+pub struct TextFormat {
+    /// Always send the text as rich text converted from Markdown
+    #[arg(long, conflicts_with = "plain")]
+    pub markdown: bool,
+
+    /// Send the text exactly as typed, without Markdown conversion
+    #[arg(long)]
+    pub plain: bool,
+}
@@ -1,12 +1,16 @@
+pub mod api;
 pub mod auth;
 pub mod messages;
 pub mod shared;
 pub mod tickets;
 
-use clap::{ArgAction, Parser, Subcommand};
+use std::io::IsTerminal;
+
+use clap::{ArgAction, Parser, Subcommand, ValueEnum};
 
 pub use auth::AuthAction;
 pub use messages::MessagesAction;
+pub use shared::{Format, OutputFormat};
 pub use tickets::{LogsAction, TicketsAction};
 
 #[derive(Parser, Debug)]
@@ -14,15 +18,84 @@ pub use tickets::{LogsAction, TicketsAction};
     name = "gleap",
     about = "Unofficial CLI for the Gleap customer support API",
     version,
-    after_help = "Environment variables:\n  GLEAP_API_KEY       Gleap API key (required)\n  GLEAP_PROJECT_ID    Gleap project ID (required)\n  GLEAP_BASE_URL      API base URL (optional, defaults to https://api.gleap.io/v3)\n\nCredentials:\n  Run `gleap auth login` to store credentials in the system keychain"
+    after_help = "Environment variables:\n  GLEAP_API_KEY       Gleap API key (required)\n  GLEAP_PROJECT_ID    Gleap project ID (required)\n  GLEAP_BASE_URL      API base URL (optional, defaults to https://api.gleap.io/v3)\n  GLEAP_RETRIES       Retries after transient failures (optional, defaults to 2)\n  GLEAP_RATE_LIMIT    Requests per minute to pace to (optional, defaults to 1000)\n  GLEAP_LOG           Log filter, e.g. `gleap=debug` (optional, overrides -v)\n\nCredentials:\n  Run `gleap auth login` to store credentials in the system keychain"
 )]
 pub struct Cli {
     #[command(subcommand)]
     pub domain: Domain,
 
-    /// Increase output verbosity (-v for requests, -vv for responses, -vvv for full debug)
+    /// Increase output verbosity (-v for requests, -vv for headers and error bodies,
+    /// -vvv for full response bodies); GLEAP_LOG overrides this
     #[arg(short, long, action = ArgAction::Count, global = true)]
     pub verbose: u8,
+
+    /// Format of log lines on stderr
+    #[arg(long, value_enum, default_value_t = LogFormat::Text, global = true)]
+    pub log_format: LogFormat,
+
+    /// Retries after transient failures (timeouts, 429, 502-504); 0 disables.
+    /// Creates and updates are only retried when they can't have been applied.
+    #[arg(
+        long,
+        value_name = "N",
+        default_value_t = 2,
+        env = "GLEAP_RETRIES",
+        global = true
+    )]
+    pub retries: u32,
+
+    /// Print the request a command would send to change data (create, update,
+    /// note, reply, or `api` with a method other than GET) instead of sending it.
+    /// Lookups the command needs are still made
+    #[arg(long, global = true)]
+    pub dry_run: bool,
+
+    /// Requests per minute to pace API calls to; 0 disables pacing
+    #[arg(
+        long,
+        value_name = "N",
+        default_value_t = 1000,
+        env = "GLEAP_RATE_LIMIT",
+        global = true
+    )]
+    pub rate_limit: u32,
+
+    /// How errors are reported on stderr (defaults to json with --format
+    /// json/jsonl when stderr isn't a terminal, text otherwise)
+    #[arg(long, value_enum, global = true)]
+    pub error_format: Option<ErrorFormat>,
+
+    #[command(flatten)]
+    pub output: OutputFormat,
+}
+
+impl Cli {
+    pub fn error_format(&self) -> ErrorFormat {
+        self.error_format.unwrap_or_else(|| {
+            let machine = matches!(self.output.format, Some(Format::Json | Format::Jsonl));
+            if machine && !std::io::stderr().is_terminal() {
+                ErrorFormat::Json
+            } else {
+                ErrorFormat::Text
+            }
+        })
+    }
+}
+
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LogFormat {
+    /// Human-readable lines
+    Text,
+    /// One JSON object per line, with the request span's fields
+    Json,
+}
+
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ErrorFormat {
+    /// `Error: ...` with a hint when there is one
+    Text,
+    /// One JSON object: {kind, exit_code, status, message, retry_after, request, hint}
+    Json,
 }
 
 #[derive(Subcommand, Debug)]
@@ -44,4 +117,7 @@ pub enum Domain {
         #[command(subcommand)]
         action: MessagesAction,
     },
+
+    /// Send an authenticated request to any API endpoint
+    Api(api::ApiArgs),
 }
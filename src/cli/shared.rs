@@ -1,4 +1,5 @@
-use clap::Args;
+use chrono::{DateTime, NaiveDate, TimeDelta, Utc};
+use clap::{Args, ValueEnum};
 
 #[derive(Args, Debug, Clone)]
 pub struct Pagination {
@@ -9,11 +10,101 @@ pub struct Pagination {
     /// Number of results to skip (for pagination)
     #[arg(short, long, default_value = "0")]
     pub skip: u64,
+
+    /// Fetch every page of results (--limit sets the page size)
+    #[arg(long)]
+    pub all: bool,
+
+    /// Fetch pages until this many results have been returned
+    #[arg(long, value_name = "N")]
+    pub max: Option<u64>,
+}
+
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    /// Pretty-printed JSON
+    Json,
+    /// One compact JSON object per line (JSON Lines)
+    Jsonl,
+    /// Aligned columns sized to the terminal
+    Table,
+    /// Comma-separated values with a header row
+    Csv,
+    /// Tab-separated values with a header row
+    Tsv,
+    /// Markdown document (transcripts)
+    Markdown,
+    /// Plain text document (transcripts)
+    Text,
+    /// Standalone HTML page (transcripts)
+    Html,
+    /// HTTP Archive 1.2 for browser devtools (network logs)
+    Har,
 }
 
 #[derive(Args, Debug, Clone)]
 pub struct OutputFormat {
-    /// Output format: json (default) or table
-    #[arg(long, default_value = "json")]
-    pub format: String,
+    /// Output format (defaults to table when stdout is a terminal, json otherwise)
+    #[arg(long, value_enum, global = true)]
+    pub format: Option<Format>,
+
+    /// Comma-separated field paths for table/csv/tsv output
+    /// (e.g. id,title,processingUser.email,customData.plan)
+    #[arg(long, value_delimiter = ',', global = true)]
+    pub columns: Option<Vec<String>>,
+}
+
+/// Parse a `--since`/`--until` value. Dates and RFC 3339 timestamps are
+/// passed through unchanged; relative durations (`30m`, `12h`, `7d`, `2w`)
+/// are resolved against the current time.
+pub fn timestamp(value: &str) -> Result<String, String> {
+    parse_timestamp(value, Utc::now())
+}
+
+fn parse_timestamp(value: &str, now: DateTime<Utc>) -> Result<String, String> {
+    if NaiveDate::parse_from_str(value, "%Y-%m-%d").is_ok()
+        || DateTime::parse_from_rfc3339(value).is_ok()
+    {
+        return Ok(value.to_string());
+    }
+
+    let invalid = || {
+        format!(
+            "invalid time '{value}': expected YYYY-MM-DD, an RFC 3339 timestamp, or a duration like 7d"
+        )
+    };
+    let split = value.len().saturating_sub(1);
+    let (amount, unit) = value.split_at_checked(split).ok_or_else(invalid)?;
+    let amount: i64 = amount.parse().map_err(|_| invalid())?;
+    let delta = match unit {
+        "m" => TimeDelta::try_minutes(amount),
+        "h" => TimeDelta::try_hours(amount),
+        "d" => TimeDelta::try_days(amount),
+        "w" => TimeDelta::try_weeks(amount),
+        _ => None,
+    }
+    .ok_or_else(invalid)?;
+
+    Ok((now - delta).to_rfc3339_opts(chrono::SecondsFormat::Secs, true))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_timestamp() {
+        let now = DateTime::parse_from_rfc3339("2026-03-10T12:00:00Z")
+            .unwrap()
+            .to_utc();
+        assert_eq!(parse_timestamp("2026-01-01", now).unwrap(), "2026-01-01");
+        assert_eq!(
+            parse_timestamp("2026-01-01T08:30:00+02:00", now).unwrap(),
+            "2026-01-01T08:30:00+02:00"
+        );
+        assert_eq!(parse_timestamp("7d", now).unwrap(), "2026-03-03T12:00:00Z");
+        assert_eq!(parse_timestamp("90m", now).unwrap(), "2026-03-10T10:30:00Z");
+        assert!(parse_timestamp("yesterday", now).is_err());
+        assert!(parse_timestamp("-d", now).is_err());
+    }
 }
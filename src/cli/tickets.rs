@@ -1,56 +1,25 @@
-use clap::Subcommand;
+use std::ffi::OsStr;
+use std::marker::PhantomData;
+
+use chrono::{DateTime, TimeDelta, Utc};
+use clap::builder::{PossibleValue, PossibleValuesParser, TypedValueParser};
+use clap::{Arg, Args, Command, Subcommand};
+use gleap::models::fields::FieldEdit;
+use gleap::models::logs::{LogLevel, StatusMatch};
+use gleap::models::message::MessageType;
+use gleap::models::ticket::{FilterValue, TicketPriority, TicketStatus, TicketType};
+
+use regex::Regex;
 
 use super::shared::Pagination;
 
 #[derive(Subcommand, Debug)]
 pub enum TicketsAction {
     /// List tickets with optional filters
-    List {
-        /// Filter by status (e.g. OPEN, INPROGRESS, DONE)
-        #[arg(long)]
-        status: Option<String>,
-
-        /// Filter by type (e.g. BUG, FEATURE_REQUEST, INQUIRY)
-        #[arg(long = "type")]
-        ticket_type: Option<String>,
-
-        /// Filter by priority (e.g. LOW, MEDIUM, HIGH)
-        #[arg(long)]
-        priority: Option<String>,
-
-        /// Sort field with direction prefix (e.g. -createdAt, priority)
-        #[arg(long, default_value = "-createdAt", allow_hyphen_values = true)]
-        sort: String,
-
-        #[command(flatten)]
-        pagination: Pagination,
-    },
+    List(ListArgs),
 
     /// Create a new ticket
-    Create {
-        /// Ticket title
-        title: String,
-
-        /// Ticket type (e.g. BUG, FEATURE_REQUEST, INQUIRY)
-        #[arg(long = "type")]
-        ticket_type: Option<String>,
-
-        /// Ticket status (e.g. OPEN, INPROGRESS, DONE)
-        #[arg(long)]
-        status: Option<String>,
-
-        /// Ticket priority (e.g. LOW, MEDIUM, HIGH)
-        #[arg(long)]
-        priority: Option<String>,
-
-        /// Ticket description
-        #[arg(long)]
-        description: Option<String>,
-
-        /// Comma-separated tags (gleap-cli is always appended)
-        #[arg(long)]
-        tags: Option<String>,
-    },
+    Create(CreateArgs),
 
     /// Get a single ticket by ID
     Get {
@@ -58,28 +27,39 @@ pub enum TicketsAction {
         id: String,
     },
 
-    /// Full-text search tickets (returns up to 30 results ranked by relevance)
-    Search {
-        /// Search query text
-        query: String,
-    },
+    /// Full-text search tickets (ranked by relevance unless --sort is given)
+    Search(SearchArgs),
 
     /// Update a ticket
-    Update {
+    Update(UpdateArgs),
+
+    /// Edit a ticket's title, status, priority, tags, description and custom
+    /// data in $EDITOR, then review and send the changes
+    Edit {
         /// Ticket ID
         id: String,
 
-        /// New status
-        #[arg(long)]
-        status: Option<String>,
+        /// Apply the changes without asking for confirmation
+        #[arg(short, long)]
+        yes: bool,
+    },
 
-        /// New priority
-        #[arg(long)]
-        priority: Option<String>,
+    /// Print the ticket's conversation as a readable transcript
+    /// (--format markdown, text or html; defaults to markdown)
+    Transcript {
+        /// Ticket ID
+        id: String,
 
-        /// New title
+        /// Leave out internal notes (e.g. when sharing with the customer)
         #[arg(long)]
-        title: Option<String>,
+        exclude_notes: bool,
+    },
+
+    /// Show who changed status, priority and assignee, with response and
+    /// resolution times
+    Timeline {
+        /// Ticket ID
+        id: String,
     },
 
     /// View logs captured with a ticket
@@ -91,17 +71,11 @@ pub enum TicketsAction {
 
 #[derive(Subcommand, Debug)]
 pub enum LogsAction {
-    /// Get JavaScript console output
-    Console {
-        /// Ticket ID
-        id: String,
-    },
+    /// Show JavaScript console output captured with the report
+    Console(ConsoleArgs),
 
-    /// Get HTTP request/response data
-    Network {
-        /// Ticket ID
-        id: String,
-    },
+    /// Summarize HTTP requests captured with the report (--format har exports them)
+    Network(NetworkArgs),
 
     /// Get ticket history (status changes, assignments, etc.)
     Activity {
@@ -109,3 +83,427 @@ pub enum LogsAction {
         id: String,
     },
 }
+
+#[derive(Args, Debug)]
+pub struct ConsoleArgs {
+    /// Ticket ID
+    pub id: String,
+
+    /// Only entries at these levels, comma-separated (debug, info, warn, error)
+    #[arg(long, value_delimiter = ',', value_parser = parse_level)]
+    pub level: Vec<LogLevel>,
+
+    /// Only entries whose message or stack trace matches this regex
+    #[arg(long, value_name = "REGEX")]
+    pub grep: Option<Regex>,
+
+    /// Only entries logged at or after this time: a duration before the
+    /// report (e.g. 30s, 2m; prefix + for after it) or an RFC 3339 timestamp
+    #[arg(long, value_name = "TIME", value_parser = parse_log_time, allow_hyphen_values = true)]
+    pub since: Option<LogTime>,
+
+    /// Only entries logged at or before this time (same formats as --since)
+    #[arg(long, value_name = "TIME", value_parser = parse_log_time, allow_hyphen_values = true)]
+    pub until: Option<LogTime>,
+
+    /// Only the last N matching entries
+    #[arg(long, value_name = "N")]
+    pub tail: Option<usize>,
+
+    /// Print full stack traces instead of the first frame
+    #[arg(long)]
+    pub stack: bool,
+}
+
+#[derive(Args, Debug)]
+pub struct NetworkArgs {
+    /// Ticket ID
+    pub id: String,
+
+    /// Only responses with these statuses, comma-separated: codes (404) or
+    /// classes (4xx, 5xx)
+    #[arg(long, value_delimiter = ',', value_name = "STATUS")]
+    pub status: Vec<StatusMatch>,
+
+    /// Only requests using these methods, comma-separated (e.g. POST,PUT)
+    #[arg(long, value_delimiter = ',')]
+    pub method: Vec<String>,
+
+    /// Only requests whose URL matches this glob (* and ?), e.g. '*/api/*'
+    #[arg(long, value_name = "GLOB")]
+    pub url_glob: Option<String>,
+
+    /// Only requests that took longer than this (e.g. 500ms, 2s)
+    #[arg(long, value_name = "DURATION", value_parser = parse_millis)]
+    pub slower_than: Option<f64>,
+
+    /// Print the requests as curl commands, with credentials redacted
+    #[arg(long)]
+    pub as_curl: bool,
+
+    /// With --as-curl, only the request numbered N in the summary (after filters)
+    #[arg(long, value_name = "N", requires = "as_curl")]
+    pub index: Option<usize>,
+
+    /// With --as-curl or --format har, also redact these headers, comma-separated
+    /// (Authorization, Proxy-Authorization, Cookie and Set-Cookie always are)
+    #[arg(
+        long,
+        value_delimiter = ',',
+        value_name = "HEADER",
+        env = "GLEAP_REDACT_HEADERS"
+    )]
+    pub redact_header: Vec<String>,
+}
+
+/// Parse a duration as milliseconds: `500ms`, `2s`, `1.5s` or a bare number
+/// of milliseconds.
+fn parse_millis(value: &str) -> Result<f64, String> {
+    let (amount, scale) = if let Some(ms) = value.strip_suffix("ms") {
+        (ms, 1.0)
+    } else if let Some(s) = value.strip_suffix('s') {
+        (s, 1000.0)
+    } else {
+        (value, 1.0)
+    };
+    amount
+        .trim()
+        .parse::<f64>()
+        .ok()
+        .filter(|amount| amount.is_finite() && *amount >= 0.0)
+        .map(|amount| amount * scale)
+        .ok_or_else(|| format!("invalid duration '{value}': expected e.g. 500ms or 2s"))
+}
+
+/// A point in time for log filters, absolute or relative to when the ticket
+/// was reported.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LogTime {
+    At(DateTime<Utc>),
+    /// Offset from the report time; negative is before it.
+    FromReport(TimeDelta),
+}
+
+impl LogTime {
+    /// Resolve against the report time, if one is needed and known.
+    pub fn resolve(self, reported_at: Option<DateTime<Utc>>) -> Option<DateTime<Utc>> {
+        match self {
+            LogTime::At(at) => Some(at),
+            LogTime::FromReport(offset) => reported_at.map(|at| at + offset),
+        }
+    }
+}
+
+fn parse_log_time(value: &str) -> Result<LogTime, String> {
+    if let Ok(at) = DateTime::parse_from_rfc3339(value) {
+        return Ok(LogTime::At(at.to_utc()));
+    }
+    let invalid = || {
+        format!(
+            "invalid time '{value}': expected a duration like 30s, 5m, +10s, or an RFC 3339 timestamp"
+        )
+    };
+    let (after, duration) = match value.strip_prefix('+') {
+        Some(rest) => (true, rest),
+        None => (false, value.strip_prefix('-').unwrap_or(value)),
+    };
+    let unit_at = duration
+        .find(|c: char| !c.is_ascii_digit())
+        .ok_or_else(invalid)?;
+    let amount: i64 = duration[..unit_at].parse().map_err(|_| invalid())?;
+    let delta = match &duration[unit_at..] {
+        "ms" => TimeDelta::try_milliseconds(amount),
+        "s" => TimeDelta::try_seconds(amount),
+        "m" => TimeDelta::try_minutes(amount),
+        "h" => TimeDelta::try_hours(amount),
+        _ => None,
+    }
+    .ok_or_else(invalid)?;
+    Ok(LogTime::FromReport(if after { delta } else { -delta }))
+}
+
+fn parse_level(value: &str) -> Result<LogLevel, String> {
+    match LogLevel::parse(value) {
+        LogLevel::Other(_) => Err(format!(
+            "unknown level '{value}' (expected debug, info, warn or error)"
+        )),
+        level => Ok(level),
+    }
+}
+
+/// Enum filters accept several values, repeated or comma-separated, and
+/// match any of them. Values are checked case-insensitively against the
+/// known variants; prefix one with `raw:` to send it to the API unchecked.
+#[derive(Args, Debug)]
+pub struct FilterArgs {
+    /// Filter by status; repeat or comma-separate to match any
+    /// (raw:VALUE sends a value not listed here)
+    #[arg(long, value_delimiter = ',', ignore_case = true,
+          value_parser = FilterParser::<TicketStatus>::new())]
+    pub status: Vec<FilterValue<TicketStatus>>,
+
+    /// Filter by type; repeat or comma-separate to match any
+    /// (raw:VALUE sends a value not listed here)
+    #[arg(long = "type", value_delimiter = ',', ignore_case = true,
+          value_parser = FilterParser::<TicketType>::new())]
+    pub ticket_type: Vec<FilterValue<TicketType>>,
+
+    /// Filter by priority; repeat or comma-separate to match any
+    /// (raw:VALUE sends a value not listed here)
+    #[arg(long, value_delimiter = ',', ignore_case = true,
+          value_parser = FilterParser::<TicketPriority>::new())]
+    pub priority: Vec<FilterValue<TicketPriority>>,
+}
+
+/// Ticket and message enums with a fixed set of documented values.
+pub trait TicketEnum: Copy + Send + Sync + 'static {
+    fn known() -> &'static [Self];
+    fn name(&self) -> &'static str;
+}
+
+impl TicketEnum for TicketStatus {
+    fn known() -> &'static [Self] {
+        &TicketStatus::KNOWN
+    }
+    fn name(&self) -> &'static str {
+        self.as_str()
+    }
+}
+
+impl TicketEnum for TicketType {
+    fn known() -> &'static [Self] {
+        &TicketType::KNOWN
+    }
+    fn name(&self) -> &'static str {
+        self.as_str()
+    }
+}
+
+impl TicketEnum for MessageType {
+    fn known() -> &'static [Self] {
+        &MessageType::KNOWN
+    }
+    fn name(&self) -> &'static str {
+        self.as_str()
+    }
+}
+
+impl TicketEnum for TicketPriority {
+    fn known() -> &'static [Self] {
+        &TicketPriority::KNOWN
+    }
+    fn name(&self) -> &'static str {
+        self.as_str()
+    }
+}
+
+/// Parses a ticket enum value case-insensitively, listing the known values
+/// in help and shell completions like a `ValueEnum` would.
+#[derive(Clone)]
+pub struct EnumParser<T>(PhantomData<T>);
+
+impl<T> EnumParser<T> {
+    pub fn new() -> Self {
+        Self(PhantomData)
+    }
+}
+
+impl<T: TicketEnum> TypedValueParser for EnumParser<T> {
+    type Value = T;
+
+    fn parse_ref(
+        &self,
+        cmd: &Command,
+        arg: Option<&Arg>,
+        value: &OsStr,
+    ) -> Result<Self::Value, clap::Error> {
+        // Reuse clap's validation for its "possible values" and "did you
+        // mean" errors.
+        let names = T::known().iter().map(TicketEnum::name);
+        let value = PossibleValuesParser::new(names).parse_ref(cmd, arg, value)?;
+        let known = T::known()
+            .iter()
+            .find(|known| known.name().eq_ignore_ascii_case(&value))
+            .expect("value was validated against the known names");
+        Ok(*known)
+    }
+
+    fn possible_values(&self) -> Option<Box<dyn Iterator<Item = PossibleValue> + '_>> {
+        Some(Box::new(
+            T::known()
+                .iter()
+                .map(|known| PossibleValue::new(known.name())),
+        ))
+    }
+}
+
+/// Like [`EnumParser`], but also lets `raw:VALUE` through unchecked.
+#[derive(Clone)]
+pub struct FilterParser<T>(EnumParser<T>);
+
+impl<T> FilterParser<T> {
+    pub fn new() -> Self {
+        Self(EnumParser::new())
+    }
+}
+
+impl<T: TicketEnum> TypedValueParser for FilterParser<T> {
+    type Value = FilterValue<T>;
+
+    fn parse_ref(
+        &self,
+        cmd: &Command,
+        arg: Option<&Arg>,
+        value: &OsStr,
+    ) -> Result<Self::Value, clap::Error> {
+        if let Some(raw) = value.to_str().and_then(|v| v.strip_prefix("raw:")) {
+            return Ok(FilterValue::Other(raw.to_string()));
+        }
+        self.0.parse_ref(cmd, arg, value).map(FilterValue::Known)
+    }
+
+    fn possible_values(&self) -> Option<Box<dyn Iterator<Item = PossibleValue> + '_>> {
+        self.0.possible_values()
+    }
+}
+
+#[derive(Args, Debug)]
+pub struct ListArgs {
+    #[command(flatten)]
+    pub filters: FilterArgs,
+
+    /// Sort field with direction prefix (e.g. -createdAt, priority)
+    #[arg(long, default_value = "-createdAt", allow_hyphen_values = true)]
+    pub sort: String,
+
+    /// Only archived tickets, or only active ones with `--archived false`
+    /// (unfiltered when not given)
+    #[arg(long, value_name = "BOOL", num_args = 0..=1, default_missing_value = "true")]
+    pub archived: Option<bool>,
+
+    /// Only tickets marked as spam, or only regular ones with `--spam false`
+    /// (unfiltered when not given)
+    #[arg(long, value_name = "BOOL", num_args = 0..=1, default_missing_value = "true")]
+    pub spam: Option<bool>,
+
+    #[command(flatten)]
+    pub pagination: Pagination,
+}
+
+#[derive(Args, Debug)]
+pub struct SearchArgs {
+    /// Search query text
+    pub query: String,
+
+    #[command(flatten)]
+    pub filters: FilterArgs,
+
+    /// Sort field with direction prefix (e.g. -createdAt); defaults to relevance
+    #[arg(long, allow_hyphen_values = true)]
+    pub sort: Option<String>,
+
+    #[command(flatten)]
+    pub pagination: Pagination,
+}
+
+#[derive(Args, Debug)]
+pub struct CreateArgs {
+    /// Ticket title
+    pub title: String,
+
+    /// Ticket type
+    #[arg(long = "type", ignore_case = true, value_parser = EnumParser::<TicketType>::new())]
+    pub ticket_type: Option<TicketType>,
+
+    /// Ticket status
+    #[arg(long, ignore_case = true, value_parser = EnumParser::<TicketStatus>::new())]
+    pub status: Option<TicketStatus>,
+
+    /// Ticket priority
+    #[arg(long, ignore_case = true, value_parser = EnumParser::<TicketPriority>::new())]
+    pub priority: Option<TicketPriority>,
+
+    /// Ticket description
+    #[arg(long)]
+    pub description: Option<String>,
+
+    /// Comma-separated tags (gleap-cli is always appended)
+    #[arg(long)]
+    pub tags: Option<String>,
+
+    /// Set a customData/formData value by path, e.g. customData.plan=pro
+    /// (repeatable; numbers, booleans and JSON are parsed, quote to force a string)
+    #[arg(long, value_name = "PATH=VALUE", value_parser = FieldEdit::parse_set)]
+    pub set: Vec<FieldEdit>,
+}
+
+#[derive(Args, Debug)]
+pub struct UpdateArgs {
+    /// Ticket ID
+    pub id: String,
+
+    /// New title
+    #[arg(long)]
+    pub title: Option<String>,
+
+    /// New status
+    #[arg(long, ignore_case = true, value_parser = EnumParser::<TicketStatus>::new())]
+    pub status: Option<TicketStatus>,
+
+    /// New priority
+    #[arg(long, ignore_case = true, value_parser = EnumParser::<TicketPriority>::new())]
+    pub priority: Option<TicketPriority>,
+
+    /// New type
+    #[arg(long = "type", ignore_case = true, value_parser = EnumParser::<TicketType>::new())]
+    pub ticket_type: Option<TicketType>,
+
+    /// New description
+    #[arg(long)]
+    pub description: Option<String>,
+
+    /// Replace all tags (comma-separated; empty string clears them)
+    #[arg(long, conflicts_with_all = ["add_tag", "remove_tag"])]
+    pub tags: Option<String>,
+
+    /// Add a tag, keeping existing ones (repeatable)
+    #[arg(long, value_name = "TAG")]
+    pub add_tag: Vec<String>,
+
+    /// Remove a tag, keeping the others (repeatable)
+    #[arg(long, value_name = "TAG")]
+    pub remove_tag: Vec<String>,
+
+    /// Archive (true) or restore (false) the ticket
+    #[arg(long, value_name = "BOOL")]
+    pub archived: Option<bool>,
+
+    /// Mark (true) or unmark (false) the ticket as spam
+    #[arg(long, value_name = "BOOL")]
+    pub spam: Option<bool>,
+
+    /// Assign to a team member by user ID
+    #[arg(long, value_name = "USER_ID", conflicts_with = "unassign")]
+    pub assignee: Option<String>,
+
+    /// Remove the current assignee
+    #[arg(long)]
+    pub unassign: bool,
+
+    /// Assign to a team by team ID
+    #[arg(long, value_name = "TEAM_ID")]
+    pub team: Option<String>,
+
+    /// Replace custom data with a JSON object (e.g. '{"plan":"pro"}')
+    #[arg(long, value_name = "JSON")]
+    pub custom_data: Option<String>,
+
+    /// Set a customData/formData value by path, e.g. customData.linkedPr=1234
+    /// (repeatable; numbers, booleans and JSON are parsed, quote to force a string)
+    #[arg(long, value_name = "PATH=VALUE", value_parser = FieldEdit::parse_set)]
+    pub set: Vec<FieldEdit>,
+
+    /// Remove a customData/formData value by path (repeatable)
+    #[arg(long, value_name = "PATH", value_parser = FieldEdit::parse_unset)]
+    pub unset: Vec<FieldEdit>,
+}
@@ -0,0 +1,86 @@
+use serde::{Serialize, Serializer};
+use serde_json::Value;
+
+use super::redact;
+
+/// A request as it would have been sent, with credentials redacted.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct RequestPreview {
+    pub method: String,
+    pub url: String,
+    #[serde(serialize_with = "header_map")]
+    pub headers: Vec<(String, String)>,
+    /// The JSON body, or the body as text if it isn't JSON.
+    pub body: Option<Value>,
+}
+
+impl RequestPreview {
+    pub(crate) fn new(request: &reqwest::Request) -> Self {
+        let headers = redact::header_values(request.headers());
+        let body = request
+            .body()
+            .and_then(|body| body.as_bytes())
+            .map(|bytes| {
+                serde_json::from_slice(bytes)
+                    .unwrap_or_else(|_| Value::String(String::from_utf8_lossy(bytes).into_owned()))
+            });
+        Self {
+            method: request.method().to_string(),
+            url: request.url().to_string(),
+            headers,
+            body,
+        }
+    }
+
+    /// The request line, headers and pretty-printed body, like an HTTP
+    /// message.
+    pub fn to_text(&self) -> String {
+        let mut text = format!("{} {}\n", self.method, self.url);
+        for (name, value) in &self.headers {
+            text.push_str(&format!("{name}: {value}\n"));
+        }
+        match &self.body {
+            Some(Value::String(body)) => text.push_str(&format!("\n{body}\n")),
+            Some(body) => {
+                let json = serde_json::to_string_pretty(body).unwrap_or_default();
+                text.push_str(&format!("\n{json}\n"));
+            }
+            None => {}
+        }
+        text
+    }
+}
+
+/// Headers as a JSON object, in the order they'd be sent.
+fn header_map<S: Serializer>(
+    headers: &[(String, String)],
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    serializer.collect_map(headers.iter().map(|(name, value)| (name, value)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_preview_redacts_credentials() {
+        let request = reqwest::Client::new()
+            .patch("https://api.gleap.io/v3/tickets/t1")
+            .bearer_auth("secret-key")
+            .header("project", "secret-project")
+            .json(&serde_json::json!({"status": "DONE"}))
+            .build()
+            .unwrap();
+        let preview = RequestPreview::new(&request);
+        assert_eq!(
+            preview.to_text(),
+            "PATCH https://api.gleap.io/v3/tickets/t1\n\
+             authorization: Bearer REDACTED\n\
+             project: REDACTED\n\
+             content-type: application/json\n\
+             \n\
+             {\n  \"status\": \"DONE\"\n}\n"
+        );
+    }
+}
@@ -1,7 +1,13 @@
+use chrono::NaiveDate;
+use url::form_urlencoded;
+
 use crate::error::AppError;
 use crate::models::message::{CreateMessageRequest, Message, MessageFilters};
 
-use super::GleapClient;
+use super::{GleapClient, Paginator};
+
+/// Page size used by [`MessagesClient::paginate`] when the filters set no limit.
+const DEFAULT_PAGE_SIZE: u64 = 100;
 
 pub struct MessagesClient<'a> {
     client: &'a GleapClient,
@@ -14,27 +20,21 @@ impl<'a> MessagesClient<'a> {
 
     /// List messages with optional filters.
     pub async fn list(&self, filters: &MessageFilters) -> Result<Vec<Message>, AppError> {
-        let mut request = self.client.get("/messages");
-
-        if let Some(ref ticket) = filters.ticket {
-            request = request.query(&[("ticket", ticket.as_str())]);
-        }
-        if let Some(ref message_type) = filters.message_type {
-            request = request.query(&[("type", message_type.as_str())]);
-        }
-        if let Some(bot) = filters.bot {
-            request = request.query(&[("bot", &bot.to_string())]);
-        }
-        if let Some(limit) = filters.limit {
-            request = request.query(&[("limit", &limit.to_string())]);
-        }
-        if let Some(skip) = filters.skip {
-            request = request.query(&[("skip", &skip.to_string())]);
-        }
-
+        let request = with_filters(self.client, filters);
         self.client.send_and_parse(request).await
     }
 
+    /// Walk all messages matching the filters, page by page. `filters.skip`
+    /// is the starting offset and `filters.limit` the page size.
+    pub fn paginate(&self, filters: &MessageFilters) -> Paginator<'a, MessageFilters> {
+        Paginator::new(
+            self.client,
+            filters.clone(),
+            filters.skip.unwrap_or(0),
+            filters.limit.unwrap_or(DEFAULT_PAGE_SIZE),
+        )
+    }
+
     /// Create a new message (comment or internal note) on a ticket.
     pub async fn create(&self, request: &CreateMessageRequest) -> Result<Message, AppError> {
         let req = self.client.post("/messages").json(request);
@@ -42,10 +42,17 @@ impl<'a> MessagesClient<'a> {
     }
 
     /// Convenience: create an internal note on a ticket.
-    pub async fn create_note(&self, ticket_id: &str, text: &str) -> Result<Message, AppError> {
+    ///
+    /// `comment` is a plain string or a rich document (see
+    /// [`crate::render::markdown::to_doc`]).
+    pub async fn create_note(
+        &self,
+        ticket_id: &str,
+        comment: impl Into<serde_json::Value>,
+    ) -> Result<Message, AppError> {
         let request = CreateMessageRequest {
             ticket: ticket_id.to_string(),
-            comment: serde_json::Value::String(text.to_string()),
+            comment: comment.into(),
             is_note: Some(true),
             session: None,
             attachments: None,
@@ -54,10 +61,17 @@ impl<'a> MessagesClient<'a> {
     }
 
     /// Convenience: create a comment reply on a ticket.
-    pub async fn create_comment(&self, ticket_id: &str, text: &str) -> Result<Message, AppError> {
+    ///
+    /// `comment` is a plain string or a rich document (see
+    /// [`crate::render::markdown::to_doc`]).
+    pub async fn create_comment(
+        &self,
+        ticket_id: &str,
+        comment: impl Into<serde_json::Value>,
+    ) -> Result<Message, AppError> {
         let request = CreateMessageRequest {
             ticket: ticket_id.to_string(),
-            comment: serde_json::Value::String(text.to_string()),
+            comment: comment.into(),
             is_note: None,
             session: None,
             attachments: None,
@@ -65,3 +79,87 @@ impl<'a> MessagesClient<'a> {
         self.create(&request).await
     }
 }
+
+fn with_filters(client: &GleapClient, filters: &MessageFilters) -> reqwest::RequestBuilder {
+    // The date range uses the spec's `createdAt>=DATE&createdAt<=DATE` form,
+    // which isn't a key/value pair, so it goes into the path's query string.
+    let mut range = Vec::new();
+    if let Some(ref since) = filters.since {
+        range.push(format!("createdAt>={}", encode(since)));
+    }
+    if let Some(ref until) = filters.until {
+        range.push(format!("createdAt<={}", encode(&end_of_day(until))));
+    }
+    let mut request = if range.is_empty() {
+        client.get("/messages")
+    } else {
+        client.get(&format!("/messages?{}", range.join("&")))
+    };
+
+    if let Some(ref ticket) = filters.ticket {
+        request = request.query(&[("ticket", ticket.as_str())]);
+    }
+    if let Some(ref message_type) = filters.message_type {
+        request = request.query(&[("type", message_type.as_str())]);
+    }
+    if let Some(bot) = filters.bot {
+        request = request.query(&[("bot", &bot.to_string())]);
+    }
+    if let Some(ref language) = filters.language {
+        request = request.query(&[("language", language.as_str())]);
+    }
+    if let Some(limit) = filters.limit {
+        request = request.query(&[("limit", &limit.to_string())]);
+    }
+    if let Some(skip) = filters.skip {
+        request = request.query(&[("skip", &skip.to_string())]);
+    }
+    request
+}
+
+fn encode(value: &str) -> String {
+    form_urlencoded::byte_serialize(value.as_bytes()).collect()
+}
+
+/// An upper bound given as a bare date covers that whole day (UTC).
+fn end_of_day(until: &str) -> String {
+    match NaiveDate::parse_from_str(until, "%Y-%m-%d") {
+        Ok(date) => format!("{date}T23:59:59.999Z"),
+        Err(_) => until.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::GleapConfig;
+
+    #[test]
+    fn test_query_string() {
+        let client = GleapClient::new(GleapConfig {
+            api_key: "test-key".into(),
+            project_id: "test-project".into(),
+            base_url: "http://localhost".into(),
+        })
+        .unwrap();
+        let filters = MessageFilters {
+            ticket: Some("t1".into()),
+            since: Some("2026-01-01T08:30:00+02:00".into()),
+            until: Some("2026-01-31".into()),
+            limit: Some(20),
+            ..Default::default()
+        };
+        let request = with_filters(&client, &filters).build().unwrap();
+
+        // URLs always percent-encode `<` and `>`; the server decodes the
+        // pairs back to `createdAt>=...` and `createdAt<=...`.
+        assert_eq!(
+            request.url().query(),
+            Some(
+                "createdAt%3E=2026-01-01T08%3A30%3A00%2B02%3A00\
+                 &createdAt%3C=2026-01-31T23%3A59%3A59.999Z\
+                 &ticket=t1&limit=20"
+            )
+        );
+    }
+}
@@ -1,11 +1,30 @@
+mod dry_run;
 mod messages;
+mod pagination;
+mod rate_limit;
+mod raw;
+mod redact;
+mod retry;
 mod tickets;
 
+pub use dry_run::RequestPreview;
 pub use messages::MessagesClient;
+pub use pagination::{Page, PageQuery, Paginator};
+pub use rate_limit::{RateLimit, RateLimiter};
+pub use raw::{RawClient, RawPages, RawRequest};
+pub use redact::{DEFAULT_REDACTED_HEADERS, REDACTED, Redaction};
+pub use retry::RetryPolicy;
 pub use tickets::TicketsClient;
 
+use std::sync::Arc;
+use std::time::Instant;
+
+use tracing::Instrument;
+
 use crate::config::GleapConfig;
-use crate::error::AppError;
+use crate::error::{ApiErrorBody, AppError};
+
+use rate_limit::Budget;
 
 /// Core Gleap API client. Holds shared HTTP client and auth configuration.
 ///
@@ -15,10 +34,20 @@ use crate::error::AppError;
 /// client.tickets().list(filters).await?;
 /// client.messages().create_note(ticket_id, text).await?;
 /// ```
+///
+/// Clones share the connection pool and the rate limiter.
+///
+/// Requests are logged as [`tracing`] events inside a `request` span: the
+/// method, URL and response status at `INFO`, headers and error bodies at
+/// `DEBUG`, and full response bodies at `TRACE`. The API key, project ID and
+/// cookies are always redacted from logged headers.
+#[derive(Clone)]
 pub struct GleapClient {
     http: reqwest::Client,
     config: GleapConfig,
-    verbose: u8,
+    retry: RetryPolicy,
+    rate_limiter: Option<Arc<RateLimiter>>,
+    dry_run: bool,
 }
 
 impl GleapClient {
@@ -31,13 +60,29 @@ impl GleapClient {
         Ok(Self {
             http,
             config,
-            verbose: 0,
+            retry: RetryPolicy::default(),
+            rate_limiter: None,
+            dry_run: false,
         })
     }
 
-    /// Set verbosity level (0=quiet, 1=requests, 2=+responses, 3=+full body).
-    pub fn with_verbose(mut self, level: u8) -> Self {
-        self.verbose = level;
+    /// Set how transient failures are retried (see [`RetryPolicy`]).
+    pub fn with_retry(mut self, policy: RetryPolicy) -> Self {
+        self.retry = policy;
+        self
+    }
+
+    /// Pace requests to stay within `limit` (see [`RateLimiter`]). Without
+    /// this, requests are sent as fast as they are made.
+    pub fn with_rate_limit(mut self, limit: RateLimit) -> Self {
+        self.rate_limiter = Some(Arc::new(RateLimiter::new(limit)));
+        self
+    }
+
+    /// Don't send requests that could change anything: the first one fails
+    /// with [`AppError::DryRun`] describing it instead. Reads still go out.
+    pub fn with_dry_run(mut self, dry_run: bool) -> Self {
+        self.dry_run = dry_run;
         self
     }
 
@@ -61,112 +106,196 @@ impl GleapClient {
         MessagesClient::new(self)
     }
 
-    /// Build a GET request with auth headers pre-applied.
-    pub(crate) fn get(&self, path: &str) -> reqwest::RequestBuilder {
+    /// Requests to endpoints without a typed client.
+    pub fn raw(&self) -> RawClient<'_> {
+        RawClient::new(self)
+    }
+
+    /// Build a request with auth headers pre-applied.
+    pub(crate) fn request(&self, method: reqwest::Method, path: &str) -> reqwest::RequestBuilder {
         let url = format!("{}{}", self.config.base_url, path);
-        if self.verbose >= 1 {
-            eprintln!("> GET {url}");
-        }
         self.http
-            .get(&url)
+            .request(method, &url)
             .bearer_auth(&self.config.api_key)
             .header("project", &self.config.project_id)
     }
 
+    /// Build a GET request with auth headers pre-applied.
+    pub(crate) fn get(&self, path: &str) -> reqwest::RequestBuilder {
+        self.request(reqwest::Method::GET, path)
+    }
+
     /// Build a POST request with auth headers pre-applied.
     pub(crate) fn post(&self, path: &str) -> reqwest::RequestBuilder {
-        let url = format!("{}{}", self.config.base_url, path);
-        if self.verbose >= 1 {
-            eprintln!("> POST {url}");
-        }
-        self.http
-            .post(&url)
-            .bearer_auth(&self.config.api_key)
-            .header("project", &self.config.project_id)
+        self.request(reqwest::Method::POST, path)
     }
 
-    /// Build a PUT request with auth headers pre-applied.
-    pub(crate) fn put(&self, path: &str) -> reqwest::RequestBuilder {
-        let url = format!("{}{}", self.config.base_url, path);
-        if self.verbose >= 1 {
-            eprintln!("> PUT {url}");
-        }
-        self.http
-            .put(&url)
-            .bearer_auth(&self.config.api_key)
-            .header("project", &self.config.project_id)
+    /// Build a PATCH request with auth headers pre-applied.
+    pub(crate) fn patch(&self, path: &str) -> reqwest::RequestBuilder {
+        self.request(reqwest::Method::PATCH, path)
     }
 
-    /// Send a request and handle common error responses.
+    /// Send a request, retrying transient failures according to the retry
+    /// policy, and handle common error responses.
     pub(crate) async fn send(
         &self,
         request: reqwest::RequestBuilder,
     ) -> Result<reqwest::Response, AppError> {
-        let start = std::time::Instant::now();
-        let response = request.send().await?;
-        let elapsed = start.elapsed();
-        let status = response.status();
-
-        if self.verbose >= 1 {
-            eprintln!(
-                "< {} {} ({:.0?})",
-                status.as_u16(),
-                status.canonical_reason().unwrap_or(""),
-                elapsed
-            );
+        let request = request.build()?;
+        let read_only = matches!(
+            *request.method(),
+            reqwest::Method::GET | reqwest::Method::HEAD | reqwest::Method::OPTIONS
+        );
+        if self.dry_run && !read_only {
+            return Err(AppError::DryRun(Box::new(RequestPreview::new(&request))));
         }
+        let span = tracing::info_span!(
+            "request",
+            method = %request.method(),
+            path = request.url().path(),
+        );
+        self.send_with_retries(request).instrument(span).await
+    }
+
+    async fn send_with_retries(
+        &self,
+        mut request: reqwest::Request,
+    ) -> Result<reqwest::Response, AppError> {
+        let mut attempt = 1;
+        loop {
+            // Streaming bodies can't be cloned, so those requests get one try.
+            let next = request.try_clone();
+            let method = request.method().clone();
 
-        if self.verbose >= 2 {
-            for (name, value) in response.headers() {
-                eprintln!("< {}: {}", name, value.to_str().unwrap_or("<binary>"));
+            if let Some(limiter) = &self.rate_limiter {
+                limiter.acquire().await;
+            }
+            tracing::info!(url = %request.url(), attempt, "sending {method} request");
+            if tracing::enabled!(tracing::Level::DEBUG) {
+                for (name, value) in redact::header_values(request.headers()) {
+                    tracing::debug!("> {name}: {value}");
+                }
             }
-        }
 
-        if status.is_success() {
-            return Ok(response);
-        }
+            let start = Instant::now();
+            let response = match self.http.execute(request).await {
+                Ok(response) => response,
+                Err(e) => match next.zip(self.retry.after_error(attempt, &method, &e)) {
+                    Some((next, delay)) => {
+                        self.wait_to_retry(attempt, &e.to_string(), delay).await;
+                        request = next;
+                        attempt += 1;
+                        continue;
+                    }
+                    None => return Err(e.into()),
+                },
+            };
+            let latency = start.elapsed();
+            let status = response.status();
+            let request_id = response
+                .headers()
+                .get("x-request-id")
+                .and_then(|value| value.to_str().ok())
+                .map(str::to_string);
 
-        let status_code = status.as_u16();
-        let body = response.text().await.unwrap_or_default();
+            tracing::info!(
+                status = status.as_u16(),
+                latency_ms = latency.as_millis() as u64,
+                request_id,
+                "{}",
+                status.canonical_reason().unwrap_or("response"),
+            );
 
-        if self.verbose >= 2 {
-            eprintln!("< Body: {body}");
-        }
+            if let Some(budget) = Budget::from_headers(response.headers()) {
+                tracing::info!(
+                    remaining = budget.remaining,
+                    limit = budget.limit,
+                    reset_secs = budget.reset,
+                    "rate limit: {budget}"
+                );
+                if let Some(limiter) = &self.rate_limiter {
+                    limiter.observe_remaining(budget.remaining);
+                }
+            }
+
+            if tracing::enabled!(tracing::Level::DEBUG) {
+                for (name, value) in redact::header_values(response.headers()) {
+                    tracing::debug!("< {name}: {value}");
+                }
+            }
+
+            if status.is_success() {
+                return Ok(response);
+            }
+
+            let status_code = status.as_u16();
+            let retry_after = response
+                .headers()
+                .get(reqwest::header::RETRY_AFTER)
+                .and_then(|value| value.to_str().ok())
+                .and_then(|value| retry::parse_retry_after(value, chrono::Utc::now()));
+
+            let retry = self
+                .retry
+                .after_status(attempt, &method, status_code, retry_after);
+            if let Some((next, delay)) = next.zip(retry) {
+                let reason = format!("{status_code} {}", status.canonical_reason().unwrap_or(""));
+                self.wait_to_retry(attempt, reason.trim_end(), delay).await;
+                request = next;
+                attempt += 1;
+                continue;
+            }
+
+            let raw = response.text().await.unwrap_or_default();
+            tracing::debug!(body = %raw, "error response body");
 
-        match status_code {
-            401 | 403 => Err(AppError::Auth(body)),
-            404 => Err(AppError::NotFound(body)),
-            429 => Err(AppError::RateLimited {
-                retry_after_secs: 60,
-            }),
-            _ => Err(AppError::ApiStatus {
-                status: status_code,
-                message: body,
-            }),
+            let mut body = Box::new(ApiErrorBody::parse(raw, request_id.as_deref()));
+            if body.message.is_none() && body.code.is_none() && body.field_errors.is_empty() {
+                body.message = status.canonical_reason().map(str::to_string);
+            }
+
+            return match status_code {
+                401 | 403 => Err(AppError::Auth {
+                    status: status_code,
+                    body,
+                }),
+                404 => Err(AppError::NotFound(body)),
+                // Without a Retry-After, assume the rate limit window (60s) must pass.
+                429 => Err(AppError::RateLimited {
+                    retry_after_secs: retry_after.map_or(60, |delay| {
+                        delay.as_secs() + u64::from(delay.subsec_nanos() > 0)
+                    }),
+                }),
+                _ => Err(AppError::ApiStatus {
+                    status: status_code,
+                    body,
+                }),
+            };
         }
     }
 
-    /// Read response body as text, log at verbose levels, then deserialize.
-    /// This preserves the raw body for debugging when deserialization fails.
+    async fn wait_to_retry(&self, attempt: u32, reason: &str, delay: std::time::Duration) {
+        tracing::info!(
+            delay_ms = delay.as_millis() as u64,
+            next_attempt = attempt + 1,
+            max_attempts = self.retry.max_attempts,
+            "{reason}; retrying in {delay:.1?}"
+        );
+        tokio::time::sleep(delay).await;
+    }
+
+    /// Read response body as text, log it at `TRACE`, then deserialize.
+    /// The raw body is logged at `DEBUG` when deserialization fails.
     pub(crate) async fn send_and_parse<T: serde::de::DeserializeOwned>(
         &self,
         request: reqwest::RequestBuilder,
     ) -> Result<T, AppError> {
         let response = self.send(request).await?;
         let text = response.text().await?;
-
-        if self.verbose >= 3 {
-            eprintln!("< Body: {text}");
-        }
+        tracing::trace!(body = %text, "response body");
 
         serde_json::from_str(&text).map_err(|e| {
-            if self.verbose >= 2 {
-                eprintln!("Deserialization error: {e}");
-                eprintln!("Raw response: {text}");
-            } else {
-                eprintln!("Deserialization error: {e}");
-                eprintln!("Hint: use -vv to see the raw API response");
-            }
+            tracing::debug!(body = %text, "unexpected response body");
             AppError::Serialization(e)
         })
     }
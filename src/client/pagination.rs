@@ -0,0 +1,305 @@
+use std::collections::VecDeque;
+use std::future::Future;
+
+use futures_util::Stream;
+
+use crate::error::AppError;
+use crate::models::message::{Message, MessageFilters};
+use crate::models::ticket::{Ticket, TicketFilters, TicketSearch};
+
+use super::GleapClient;
+
+/// One page of results from a list endpoint.
+#[derive(Debug, Clone)]
+pub struct Page<T> {
+    pub items: Vec<T>,
+    /// Total number of matching items, if the endpoint reports it.
+    pub total_count: Option<u64>,
+}
+
+/// A list query that can be fetched page by page using `limit`/`skip`.
+pub trait PageQuery {
+    type Item;
+
+    fn fetch_page(
+        &self,
+        client: &GleapClient,
+        skip: u64,
+        limit: u64,
+    ) -> impl Future<Output = Result<Page<Self::Item>, AppError>> + Send;
+}
+
+impl PageQuery for TicketFilters {
+    type Item = Ticket;
+
+    async fn fetch_page(
+        &self,
+        client: &GleapClient,
+        skip: u64,
+        limit: u64,
+    ) -> Result<Page<Ticket>, AppError> {
+        let filters = TicketFilters {
+            skip: Some(skip),
+            limit: Some(limit),
+            ..self.clone()
+        };
+        let response = client.tickets().list(&filters).await?;
+        Ok(Page {
+            items: response.tickets,
+            total_count: response.total_count,
+        })
+    }
+}
+
+impl PageQuery for TicketSearch {
+    type Item = Ticket;
+
+    async fn fetch_page(
+        &self,
+        client: &GleapClient,
+        skip: u64,
+        limit: u64,
+    ) -> Result<Page<Ticket>, AppError> {
+        let search = TicketSearch {
+            query: self.query.clone(),
+            filters: TicketFilters {
+                skip: Some(skip),
+                limit: Some(limit),
+                ..self.filters.clone()
+            },
+        };
+        let response = client.tickets().search(&search).await?;
+        Ok(Page {
+            items: response.tickets,
+            total_count: response.total_count,
+        })
+    }
+}
+
+impl PageQuery for MessageFilters {
+    type Item = Message;
+
+    async fn fetch_page(
+        &self,
+        client: &GleapClient,
+        skip: u64,
+        limit: u64,
+    ) -> Result<Page<Message>, AppError> {
+        let filters = MessageFilters {
+            skip: Some(skip),
+            limit: Some(limit),
+            ..self.clone()
+        };
+        let items = client.messages().list(&filters).await?;
+        Ok(Page {
+            items,
+            total_count: None,
+        })
+    }
+}
+
+/// Lazily walks a list endpoint, fetching the next page only when the
+/// current one is used up.
+///
+/// Paging stops when the reported total has been reached, or (for endpoints
+/// without a total) when a page comes back shorter than requested.
+/// ```ignore
+/// let mut pages = client.tickets().paginate(&filters).max_items(500);
+/// while let Some(ticket) = pages.next_item().await? {
+///     println!("{}", ticket.id);
+/// }
+/// ```
+pub struct Paginator<'a, Q: PageQuery> {
+    client: &'a GleapClient,
+    query: Q,
+    skip: u64,
+    page_size: u64,
+    remaining: Option<u64>,
+    total_count: Option<u64>,
+    buffer: VecDeque<Q::Item>,
+    done: bool,
+}
+
+impl<'a, Q: PageQuery> Paginator<'a, Q> {
+    pub(crate) fn new(client: &'a GleapClient, query: Q, skip: u64, page_size: u64) -> Self {
+        Self {
+            client,
+            query,
+            skip,
+            page_size: page_size.max(1),
+            remaining: None,
+            total_count: None,
+            buffer: VecDeque::new(),
+            done: false,
+        }
+    }
+
+    /// Stop after yielding `max` items in total.
+    pub fn max_items(mut self, max: u64) -> Self {
+        self.remaining = Some(max);
+        self
+    }
+
+    /// Total number of matching items, once the first page has been fetched
+    /// (tickets only; the messages endpoint does not report a total).
+    pub fn total_count(&self) -> Option<u64> {
+        self.total_count
+    }
+
+    /// Fetch the next page. Returns `None` once all items have been yielded.
+    pub async fn next_page(&mut self) -> Result<Option<Vec<Q::Item>>, AppError> {
+        if !self.buffer.is_empty() {
+            return Ok(Some(self.buffer.drain(..).collect()));
+        }
+        if self.done || self.remaining == Some(0) {
+            return Ok(None);
+        }
+
+        let limit = match self.remaining {
+            Some(remaining) => remaining.min(self.page_size),
+            None => self.page_size,
+        };
+        let page = self.query.fetch_page(self.client, self.skip, limit).await?;
+        let mut items = page.items;
+        let received = items.len() as u64;
+        self.skip += received;
+        self.total_count = page.total_count.or(self.total_count);
+
+        // Prefer the reported total: a server-side cap on `limit` would
+        // otherwise look like a short final page.
+        self.done = match self.total_count {
+            Some(total) => received == 0 || self.skip >= total,
+            None => received < limit,
+        };
+
+        if let Some(remaining) = self.remaining.as_mut() {
+            items.truncate(*remaining as usize);
+            *remaining -= items.len() as u64;
+        }
+
+        if items.is_empty() {
+            self.done = true;
+            return Ok(None);
+        }
+        Ok(Some(items))
+    }
+
+    /// Yield the next item, fetching a new page when needed.
+    pub async fn next_item(&mut self) -> Result<Option<Q::Item>, AppError> {
+        if self.buffer.is_empty() {
+            match self.next_page().await? {
+                Some(page) => self.buffer.extend(page),
+                None => return Ok(None),
+            }
+        }
+        Ok(self.buffer.pop_front())
+    }
+
+    /// Fetch every remaining page and return all items.
+    pub async fn collect_all(mut self) -> Result<Vec<Q::Item>, AppError> {
+        let mut items = Vec::new();
+        while let Some(page) = self.next_page().await? {
+            items.extend(page);
+        }
+        Ok(items)
+    }
+
+    /// Convert into a `Stream` of items for use with `futures` combinators.
+    pub fn into_stream(self) -> impl Stream<Item = Result<Q::Item, AppError>> + 'a
+    where
+        Q: 'a,
+    {
+        futures_util::stream::try_unfold(self, |mut pages| async move {
+            Ok(pages.next_item().await?.map(|item| (item, pages)))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::GleapConfig;
+
+    /// Serves `total` numbered items, optionally reporting the total and
+    /// capping the page size like a server-side limit would.
+    struct Numbers {
+        total: u64,
+        report_total: bool,
+        server_cap: u64,
+    }
+
+    impl PageQuery for Numbers {
+        type Item = u64;
+
+        async fn fetch_page(
+            &self,
+            _client: &GleapClient,
+            skip: u64,
+            limit: u64,
+        ) -> Result<Page<u64>, AppError> {
+            let end = (skip + limit.min(self.server_cap)).min(self.total);
+            Ok(Page {
+                items: (skip..end).collect(),
+                total_count: self.report_total.then_some(self.total),
+            })
+        }
+    }
+
+    fn client() -> GleapClient {
+        GleapClient::new(GleapConfig {
+            api_key: "test-key".into(),
+            project_id: "test-project".into(),
+            base_url: "http://localhost".into(),
+        })
+        .unwrap()
+    }
+
+    fn numbers(total: u64, report_total: bool, server_cap: u64) -> Numbers {
+        Numbers {
+            total,
+            report_total,
+            server_cap,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_collects_last_partial_page() {
+        let client = client();
+        let items = Paginator::new(&client, numbers(45, false, u64::MAX), 0, 20)
+            .collect_all()
+            .await
+            .unwrap();
+        assert_eq!(items, (0..45).collect::<Vec<_>>());
+    }
+
+    #[tokio::test]
+    async fn test_exact_multiple_of_page_size() {
+        let client = client();
+        let mut pages = Paginator::new(&client, numbers(40, true, u64::MAX), 0, 20);
+        assert_eq!(pages.next_page().await.unwrap().map(|p| p.len()), Some(20));
+        assert_eq!(pages.total_count(), Some(40));
+        assert_eq!(pages.next_page().await.unwrap().map(|p| p.len()), Some(20));
+        assert!(pages.next_page().await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_total_count_survives_server_cap() {
+        let client = client();
+        let items = Paginator::new(&client, numbers(25, true, 10), 0, 20)
+            .collect_all()
+            .await
+            .unwrap();
+        assert_eq!(items.len(), 25);
+    }
+
+    #[tokio::test]
+    async fn test_max_items_and_skip() {
+        let client = client();
+        let mut pages = Paginator::new(&client, numbers(100, true, u64::MAX), 5, 20).max_items(3);
+        let mut items = Vec::new();
+        while let Some(item) = pages.next_item().await.unwrap() {
+            items.push(item);
+        }
+        assert_eq!(items, vec![5, 6, 7]);
+    }
+}
@@ -0,0 +1,195 @@
+//! Client-side pacing for the per-project request budget.
+//!
+//! The API allows 1000 requests per 60 seconds per project. A token bucket
+//! holds up to that many requests and refills at the same average rate, so
+//! a burst goes out at full speed and sustained traffic slows down to the
+//! budget instead of running into `429`s. When the server reports how much
+//! of the budget is left (other clients may be using it too), the bucket
+//! never holds more than that.
+
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use reqwest::header::HeaderMap;
+
+/// A request budget: `requests` per `per`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RateLimit {
+    pub requests: u32,
+    pub per: Duration,
+}
+
+impl RateLimit {
+    /// The documented per-project limit.
+    pub const PROJECT: Self = Self {
+        requests: 1000,
+        per: Duration::from_secs(60),
+    };
+
+    /// `requests` per minute.
+    pub fn per_minute(requests: u32) -> Self {
+        Self {
+            requests,
+            per: Duration::from_secs(60),
+        }
+    }
+}
+
+/// A token bucket shared by every request made through a client (and its
+/// clones).
+#[derive(Debug)]
+pub struct RateLimiter {
+    capacity: f64,
+    /// Tokens added per second.
+    rate: f64,
+    bucket: Mutex<Bucket>,
+}
+
+#[derive(Debug)]
+struct Bucket {
+    /// May go negative: each waiting request has reserved a future token.
+    tokens: f64,
+    updated: Instant,
+}
+
+impl RateLimiter {
+    /// A limiter that starts with a full bucket.
+    ///
+    /// # Panics
+    ///
+    /// If the limit allows no requests or has a zero period.
+    pub fn new(limit: RateLimit) -> Self {
+        assert!(
+            limit.requests > 0 && !limit.per.is_zero(),
+            "rate limit must allow some requests"
+        );
+        let capacity = f64::from(limit.requests);
+        Self {
+            capacity,
+            rate: capacity / limit.per.as_secs_f64(),
+            bucket: Mutex::new(Bucket {
+                tokens: capacity,
+                updated: Instant::now(),
+            }),
+        }
+    }
+
+    /// Wait until a request may be sent. Requests go out in the order they
+    /// called this.
+    pub async fn acquire(&self) {
+        let wait = self.reserve(Instant::now());
+        if !wait.is_zero() {
+            tokio::time::sleep(wait).await;
+        }
+    }
+
+    /// Take a token, returning how long to wait for it to become available.
+    fn reserve(&self, now: Instant) -> Duration {
+        let mut bucket = self.bucket.lock().unwrap_or_else(|e| e.into_inner());
+        self.refill(&mut bucket, now);
+        bucket.tokens -= 1.0;
+        if bucket.tokens >= 0.0 {
+            Duration::ZERO
+        } else {
+            Duration::from_secs_f64(-bucket.tokens / self.rate)
+        }
+    }
+
+    /// Account for the server's view of the remaining budget, which also
+    /// counts other clients on the same project.
+    pub fn observe_remaining(&self, remaining: u32) {
+        let mut bucket = self.bucket.lock().unwrap_or_else(|e| e.into_inner());
+        self.refill(&mut bucket, Instant::now());
+        bucket.tokens = bucket.tokens.min(f64::from(remaining));
+    }
+
+    fn refill(&self, bucket: &mut Bucket, now: Instant) {
+        let elapsed = now.saturating_duration_since(bucket.updated).as_secs_f64();
+        bucket.tokens = (bucket.tokens + elapsed * self.rate).min(self.capacity);
+        bucket.updated = now;
+    }
+}
+
+/// The budget a response reports, from `X-RateLimit-*` or `RateLimit-*`
+/// headers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct Budget {
+    pub remaining: u32,
+    pub limit: Option<u32>,
+    /// Seconds until the budget resets.
+    pub reset: Option<u64>,
+}
+
+impl Budget {
+    pub(crate) fn from_headers(headers: &HeaderMap) -> Option<Self> {
+        let number = |name: &str| {
+            [format!("x-ratelimit-{name}"), format!("ratelimit-{name}")]
+                .iter()
+                .find_map(|header| headers.get(header.as_str()))
+                .and_then(|value| value.to_str().ok())
+                .and_then(|value| value.trim().parse::<u64>().ok())
+        };
+        Some(Self {
+            remaining: u32::try_from(number("remaining")?).unwrap_or(u32::MAX),
+            limit: number("limit").and_then(|limit| u32::try_from(limit).ok()),
+            reset: number("reset"),
+        })
+    }
+}
+
+impl std::fmt::Display for Budget {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.limit {
+            Some(limit) => write!(f, "{}/{limit} requests left", self.remaining)?,
+            None => write!(f, "{} requests left", self.remaining)?,
+        }
+        if let Some(reset) = self.reset {
+            write!(f, ", resets in {reset}s")?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bucket_paces_after_burst() {
+        let limiter = RateLimiter::new(RateLimit {
+            requests: 2,
+            per: Duration::from_secs(1),
+        });
+        let start = Instant::now();
+        assert_eq!(limiter.reserve(start), Duration::ZERO);
+        assert_eq!(limiter.reserve(start), Duration::ZERO);
+        // Tokens come back at 2 per second; each waiter queues behind the last.
+        assert_eq!(limiter.reserve(start), Duration::from_millis(500));
+        assert_eq!(limiter.reserve(start), Duration::from_millis(1000));
+        // Idle time refills the bucket, but never beyond its capacity.
+        let later = start + Duration::from_secs(10);
+        assert_eq!(limiter.reserve(later), Duration::ZERO);
+        assert_eq!(limiter.reserve(later), Duration::ZERO);
+        assert_eq!(limiter.reserve(later), Duration::from_millis(500));
+    }
+
+    #[test]
+    fn test_server_budget_caps_bucket() {
+        let limiter = RateLimiter::new(RateLimit::per_minute(1000));
+        limiter.observe_remaining(1);
+        assert_eq!(limiter.reserve(Instant::now()), Duration::ZERO);
+        assert!(limiter.reserve(Instant::now()) > Duration::ZERO);
+    }
+
+    #[test]
+    fn test_budget_from_headers() {
+        let mut headers = HeaderMap::new();
+        assert_eq!(Budget::from_headers(&headers), None);
+
+        headers.insert("x-ratelimit-remaining", "998".parse().unwrap());
+        headers.insert("x-ratelimit-limit", "1000".parse().unwrap());
+        headers.insert("ratelimit-reset", "42".parse().unwrap());
+        let budget = Budget::from_headers(&headers).unwrap();
+        assert_eq!(budget.to_string(), "998/1000 requests left, resets in 42s");
+    }
+}
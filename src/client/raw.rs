@@ -0,0 +1,225 @@
+use reqwest::Method;
+use serde_json::Value;
+
+use crate::error::AppError;
+
+use super::{GleapClient, Page, PageQuery, Paginator};
+
+/// A request to an arbitrary endpoint, for the parts of the API without a
+/// typed client.
+#[derive(Debug, Clone)]
+pub struct RawRequest {
+    pub method: Method,
+    /// Path relative to the base URL, e.g. `/tickets/ID/activitylogs`.
+    pub path: String,
+    pub query: Vec<(String, String)>,
+    /// JSON body, if any.
+    pub body: Option<Value>,
+}
+
+impl RawRequest {
+    pub fn new(method: Method, path: &str) -> Self {
+        let path = if path.starts_with('/') {
+            path.to_string()
+        } else {
+            format!("/{path}")
+        };
+        Self {
+            method,
+            path,
+            query: Vec::new(),
+            body: None,
+        }
+    }
+}
+
+pub struct RawClient<'a> {
+    client: &'a GleapClient,
+}
+
+impl<'a> RawClient<'a> {
+    pub(crate) fn new(client: &'a GleapClient) -> Self {
+        Self { client }
+    }
+
+    /// Send the request with the client's auth, retries and rate limit.
+    /// Returns the response body as JSON: `null` when empty, or a string
+    /// when it isn't JSON.
+    pub async fn send(&self, request: &RawRequest) -> Result<Value, AppError> {
+        let mut builder = self
+            .client
+            .request(request.method.clone(), &request.path)
+            .query(&request.query);
+        if let Some(body) = &request.body {
+            builder = builder.json(body);
+        }
+        let text = self.client.send(builder).await?.text().await?;
+        tracing::trace!(body = %text, "response body");
+
+        if text.trim().is_empty() {
+            return Ok(Value::Null);
+        }
+        Ok(serde_json::from_str(&text).unwrap_or(Value::String(text)))
+    }
+
+    /// Walk a list endpoint with `skip`/`limit` query parameters, starting
+    /// at `skip` with pages of `page_size`.
+    pub fn paginate(&self, request: &RawRequest, skip: u64, page_size: u64) -> RawPages<'a> {
+        RawPages {
+            pages: Paginator::new(self.client, request.clone(), skip, page_size),
+            path: request.path.clone(),
+            previous: None,
+            fetched: 0,
+        }
+    }
+}
+
+/// Most pages [`RawPages`] fetches before giving up.
+pub const MAX_PAGES: u64 = 1000;
+
+/// Pages of an arbitrary list endpoint. Since the endpoint may not support
+/// `skip`/`limit` at all, paging fails once a page repeats the previous one
+/// or after [`MAX_PAGES`] pages, instead of requesting the same page forever.
+pub struct RawPages<'a> {
+    pages: Paginator<'a, RawRequest>,
+    path: String,
+    previous: Option<Vec<Value>>,
+    fetched: u64,
+}
+
+impl RawPages<'_> {
+    /// Fetch the next page. Returns `None` once all items have been yielded.
+    pub async fn next_page(&mut self) -> Result<Option<Vec<Value>>, AppError> {
+        let Some(page) = self.pages.next_page().await? else {
+            return Ok(None);
+        };
+        self.fetched += 1;
+        check_progress(&self.path, self.previous.as_deref(), &page, self.fetched)?;
+        self.previous = Some(page.clone());
+        Ok(Some(page))
+    }
+}
+
+fn check_progress(
+    path: &str,
+    previous: Option<&[Value]>,
+    page: &[Value],
+    fetched: u64,
+) -> Result<(), AppError> {
+    if previous == Some(page) {
+        return Err(AppError::Api(format!(
+            "{path} returned the same page twice; it doesn't seem to support skip/limit paging"
+        )));
+    }
+    if fetched > MAX_PAGES {
+        return Err(AppError::Api(format!(
+            "stopped paging {path} after {MAX_PAGES} pages"
+        )));
+    }
+    Ok(())
+}
+
+impl PageQuery for RawRequest {
+    type Item = Value;
+
+    async fn fetch_page(
+        &self,
+        client: &GleapClient,
+        skip: u64,
+        limit: u64,
+    ) -> Result<Page<Value>, AppError> {
+        let mut request = self.clone();
+        request
+            .query
+            .retain(|(key, _)| key != "skip" && key != "limit");
+        request.query.extend([
+            ("skip".into(), skip.to_string()),
+            ("limit".into(), limit.to_string()),
+        ]);
+        page_items(&self.path, client.raw().send(&request).await?)
+    }
+}
+
+/// The items of a list response: the response itself when it's an array,
+/// or else the only array in the response object (e.g. `tickets`), with
+/// `totalCount` as the total. An object holding several arrays is an error,
+/// since there's no telling which one is the list.
+fn page_items(path: &str, response: Value) -> Result<Page<Value>, AppError> {
+    let not_a_list = || {
+        AppError::Config(format!(
+            "{path} didn't return a list, so it can't be paginated"
+        ))
+    };
+    match response {
+        Value::Array(items) => Ok(Page {
+            items,
+            total_count: None,
+        }),
+        Value::Object(map) => {
+            let total_count = map.get("totalCount").and_then(Value::as_u64);
+            let mut arrays: Vec<(String, Vec<Value>)> = map
+                .into_iter()
+                .filter_map(|(key, value)| match value {
+                    Value::Array(items) => Some((key, items)),
+                    _ => None,
+                })
+                .collect();
+            match arrays.len() {
+                0 => Err(not_a_list()),
+                1 => Ok(Page {
+                    items: arrays.remove(0).1,
+                    total_count,
+                }),
+                _ => {
+                    let keys: Vec<&str> = arrays.iter().map(|(key, _)| key.as_str()).collect();
+                    Err(AppError::Config(format!(
+                        "{path} returned several lists ({}), so it can't be paginated",
+                        keys.join(", ")
+                    )))
+                }
+            }
+        }
+        _ => Err(not_a_list()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_page_items() {
+        let page = page_items("/x", json!([1, 2])).unwrap();
+        assert_eq!(
+            (page.items, page.total_count),
+            (vec![json!(1), json!(2)], None)
+        );
+
+        let response = json!({ "count": 1, "tickets": [{ "id": "t1" }], "totalCount": 7 });
+        let page = page_items("/tickets", response).unwrap();
+        assert_eq!(page.items, vec![json!({ "id": "t1" })]);
+        assert_eq!(page.total_count, Some(7));
+
+        let error = page_items("/x", json!({ "items": [1], "tags": ["a"] })).unwrap_err();
+        assert!(error.to_string().contains("several lists (items, tags)"));
+        assert!(page_items("/x", json!({ "id": "t1" })).is_err());
+        assert!(page_items("/x", Value::Null).is_err());
+    }
+
+    #[test]
+    fn test_check_progress_stops_runaway_paging() {
+        let page = [json!(1), json!(2)];
+        let next = [json!(3), json!(4)];
+        assert!(check_progress("/x", None, &page, 1).is_ok());
+        assert!(check_progress("/x", Some(&page), &next, 2).is_ok());
+        assert!(check_progress("/x", Some(&page), &page, 2).is_err());
+        assert!(check_progress("/x", Some(&page), &next, MAX_PAGES + 1).is_err());
+    }
+
+    #[test]
+    fn test_new_adds_leading_slash() {
+        assert_eq!(RawRequest::new(Method::GET, "tickets").path, "/tickets");
+        assert_eq!(RawRequest::new(Method::GET, "/tickets").path, "/tickets");
+    }
+}
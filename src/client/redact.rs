@@ -0,0 +1,98 @@
+//! Header redaction shared by request logging, `--dry-run` previews and
+//! the captured-request exports (curl commands, HAR).
+//!
+//! Redacted headers keep their name (and, for `Authorization`, the scheme)
+//! so output shows where a credential goes, but never the credential.
+
+/// Headers whose values are always redacted.
+pub const DEFAULT_REDACTED_HEADERS: &[&str] = &[
+    "authorization",
+    "proxy-authorization",
+    "cookie",
+    "set-cookie",
+];
+
+/// Placeholder written in place of a redacted value.
+pub const REDACTED: &str = "REDACTED";
+
+/// Which header values to hide. Names compare case-insensitively.
+#[derive(Debug, Clone)]
+pub struct Redaction {
+    headers: Vec<String>,
+}
+
+impl Redaction {
+    /// The default headers plus `extra` ones, e.g. `X-Api-Key`.
+    pub fn new<S: AsRef<str>>(extra: &[S]) -> Self {
+        let headers = DEFAULT_REDACTED_HEADERS
+            .iter()
+            .copied()
+            .chain(extra.iter().map(AsRef::as_ref))
+            .map(str::to_ascii_lowercase)
+            .collect();
+        Self { headers }
+    }
+
+    fn applies_to(&self, name: &str) -> bool {
+        self.headers.iter().any(|h| h.eq_ignore_ascii_case(name))
+    }
+
+    /// The value to print for a header: unchanged unless it's redacted.
+    /// Cookies keep their names (and `Set-Cookie` its attributes) and
+    /// `Authorization` keeps its scheme.
+    pub fn header_value(&self, name: &str, value: &str) -> String {
+        if !self.applies_to(name) {
+            return value.to_string();
+        }
+        if name.eq_ignore_ascii_case("set-cookie") {
+            let (cookie, attributes) = value.split_once(';').unwrap_or((value, ""));
+            let cookie = match cookie.split_once('=') {
+                Some((name, _)) => format!("{}={REDACTED}", name.trim()),
+                None => REDACTED.to_string(),
+            };
+            return match attributes {
+                "" => cookie,
+                attributes => format!("{cookie};{attributes}"),
+            };
+        }
+        if name.eq_ignore_ascii_case("cookie") {
+            return value
+                .split(';')
+                .map(|cookie| match cookie.trim().split_once('=') {
+                    Some((name, _)) => format!("{name}={REDACTED}"),
+                    None => REDACTED.to_string(),
+                })
+                .collect::<Vec<_>>()
+                .join("; ");
+        }
+        match value.split_once(' ') {
+            Some((scheme, _)) if name.to_ascii_lowercase().ends_with("authorization") => {
+                format!("{scheme} {REDACTED}")
+            }
+            _ => REDACTED.to_string(),
+        }
+    }
+}
+
+impl Default for Redaction {
+    fn default() -> Self {
+        Self::new::<&str>(&[])
+    }
+}
+
+/// Header values safe to show or log, with credentials and cookies redacted.
+pub(crate) fn header_values(headers: &reqwest::header::HeaderMap) -> Vec<(String, String)> {
+    // The project ID isn't a secret on its own, but it's half of what
+    // authenticates a request.
+    let redaction = Redaction::new(&["project"]);
+    headers
+        .iter()
+        .map(|(name, value)| {
+            let value = value.to_str().unwrap_or("<binary>");
+            (
+                name.to_string(),
+                redaction.header_value(name.as_str(), value),
+            )
+        })
+        .collect()
+}
@@ -0,0 +1,188 @@
+//! Retrying transient failures with exponential backoff.
+//!
+//! A request is retried when the server answers with a retryable status or
+//! the connection fails. Methods that aren't idempotent (POST, PATCH) are
+//! only retried when the server can't have acted on the request: a `429`,
+//! or a connection that was never established. That way a create is never
+//! sent twice.
+
+use std::hash::{BuildHasher, RandomState};
+use std::time::{Duration, SystemTime};
+
+use chrono::{DateTime, Utc};
+use reqwest::Method;
+
+/// When and how often [`GleapClient`](super::GleapClient) retries a request.
+#[derive(Debug, Clone)]
+pub struct RetryPolicy {
+    /// Total attempts, including the first. `1` disables retries.
+    pub max_attempts: u32,
+    /// Delay before the first retry; it doubles for each retry after that.
+    /// The actual delay is randomized between half and all of it.
+    pub base_delay: Duration,
+    /// Longest single wait. A `Retry-After` beyond this isn't waited out:
+    /// the error is returned with the server's delay instead.
+    pub max_delay: Duration,
+    /// Response statuses worth retrying.
+    pub statuses: Vec<u16>,
+    /// Methods that are safe to repeat after any retryable failure.
+    pub idempotent_methods: Vec<Method>,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: 3,
+            base_delay: Duration::from_millis(500),
+            max_delay: Duration::from_secs(30),
+            statuses: vec![408, 429, 502, 503, 504],
+            idempotent_methods: vec![
+                Method::GET,
+                Method::HEAD,
+                Method::OPTIONS,
+                Method::PUT,
+                Method::DELETE,
+            ],
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// A policy that never retries.
+    pub fn none() -> Self {
+        Self {
+            max_attempts: 1,
+            ..Self::default()
+        }
+    }
+
+    /// The default policy with `retries` retries after the first attempt.
+    pub fn with_retries(retries: u32) -> Self {
+        Self {
+            max_attempts: retries.saturating_add(1),
+            ..Self::default()
+        }
+    }
+
+    /// How long to wait before retrying after attempt number `attempt`
+    /// (starting at 1) got `status`, or `None` to give up.
+    pub(crate) fn after_status(
+        &self,
+        attempt: u32,
+        method: &Method,
+        status: u16,
+        retry_after: Option<Duration>,
+    ) -> Option<Duration> {
+        if attempt >= self.max_attempts || !self.statuses.contains(&status) {
+            return None;
+        }
+        // A 429 means the request was turned away before it was handled.
+        if status != 429 && !self.idempotent_methods.contains(method) {
+            return None;
+        }
+        let delay = retry_after.unwrap_or_else(|| self.backoff(attempt));
+        (delay <= self.max_delay).then_some(delay)
+    }
+
+    /// How long to wait before retrying after attempt number `attempt`
+    /// failed without a response, or `None` to give up.
+    pub(crate) fn after_error(
+        &self,
+        attempt: u32,
+        method: &Method,
+        error: &reqwest::Error,
+    ) -> Option<Duration> {
+        if attempt >= self.max_attempts {
+            return None;
+        }
+        let retryable = if self.idempotent_methods.contains(method) {
+            error.is_connect() || error.is_timeout() || error.is_request()
+        } else {
+            error.is_connect()
+        };
+        retryable.then(|| self.backoff(attempt))
+    }
+
+    /// Exponential backoff with jitter for the retry after `attempt`.
+    fn backoff(&self, attempt: u32) -> Duration {
+        let exponential = self
+            .base_delay
+            .saturating_mul(2u32.saturating_pow(attempt.saturating_sub(1)));
+        let delay = exponential.min(self.max_delay);
+        delay.mul_f64(0.5 + random_fraction() / 2.0)
+    }
+}
+
+/// Parse a `Retry-After` header: a number of seconds or an HTTP date.
+pub(crate) fn parse_retry_after(value: &str, now: DateTime<Utc>) -> Option<Duration> {
+    let value = value.trim();
+    if let Ok(secs) = value.parse::<u64>() {
+        return Some(Duration::from_secs(secs));
+    }
+    let at = DateTime::parse_from_rfc2822(value).ok()?.to_utc();
+    Some((at - now).to_std().unwrap_or(Duration::ZERO))
+}
+
+/// A number in `[0, 1)`, random enough to spread out retries from
+/// concurrent clients.
+fn random_fraction() -> f64 {
+    let bits = RandomState::new().hash_one(SystemTime::now());
+    (bits >> 11) as f64 / (1u64 << 53) as f64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_backoff_doubles_within_jitter() {
+        let policy = RetryPolicy {
+            max_attempts: 10,
+            ..RetryPolicy::default()
+        };
+        for (attempt, full) in [(1, 500), (2, 1_000), (3, 2_000), (8, 30_000)] {
+            let delay = policy.backoff(attempt).as_millis() as u64;
+            assert!(
+                (full / 2..=full).contains(&delay),
+                "attempt {attempt}: {delay}ms not within {full}ms"
+            );
+        }
+    }
+
+    #[test]
+    fn test_status_retries_respect_idempotency() {
+        let policy = RetryPolicy::default();
+        let wait = Some(Duration::from_secs(2));
+
+        assert_eq!(policy.after_status(1, &Method::GET, 503, wait), wait);
+        assert_eq!(policy.after_status(1, &Method::POST, 503, wait), None);
+        assert_eq!(policy.after_status(1, &Method::POST, 429, wait), wait);
+        assert_eq!(policy.after_status(1, &Method::GET, 404, None), None);
+        assert_eq!(policy.after_status(3, &Method::GET, 503, wait), None);
+        assert!(policy.after_status(2, &Method::GET, 502, None).is_some());
+
+        let too_long = Some(Duration::from_secs(120));
+        assert_eq!(policy.after_status(1, &Method::GET, 429, too_long), None);
+        assert_eq!(
+            RetryPolicy::none().after_status(1, &Method::GET, 503, None),
+            None
+        );
+    }
+
+    #[test]
+    fn test_parse_retry_after() {
+        let now = DateTime::parse_from_rfc3339("2026-03-01T10:00:00Z")
+            .unwrap()
+            .to_utc();
+        assert_eq!(parse_retry_after("12", now), Some(Duration::from_secs(12)));
+        assert_eq!(
+            parse_retry_after("Sun, 01 Mar 2026 10:00:30 GMT", now),
+            Some(Duration::from_secs(30))
+        );
+        assert_eq!(
+            parse_retry_after("Sun, 01 Mar 2026 09:00:00 GMT", now),
+            Some(Duration::ZERO)
+        );
+        assert_eq!(parse_retry_after("soon", now), None);
+    }
+}
@@ -1,7 +1,16 @@
+use std::fmt;
+
 use crate::error::AppError;
-use crate::models::ticket::{Ticket, TicketFilters, TicketListResponse};
+use crate::models::logs::{self, ActivityLog, ConsoleLog, NetworkLog};
+use crate::models::ticket::{
+    CreateTicketRequest, FilterValue, Ticket, TicketFilters, TicketListResponse, TicketSearch,
+    UpdateTicketRequest,
+};
+
+use super::{GleapClient, Paginator};
 
-use super::GleapClient;
+/// Page size used by [`TicketsClient::paginate`] when the filters set no limit.
+const DEFAULT_PAGE_SIZE: u64 = 100;
 
 pub struct TicketsClient<'a> {
     client: &'a GleapClient,
@@ -14,92 +23,135 @@ impl<'a> TicketsClient<'a> {
 
     /// List tickets with optional filters.
     pub async fn list(&self, filters: &TicketFilters) -> Result<TicketListResponse, AppError> {
-        let mut request = self.client.get("/tickets");
-
-        if let Some(ref status) = filters.status {
-            request = request.query(&[("status", status.as_str())]);
-        }
-        if let Some(ref ticket_type) = filters.ticket_type {
-            request = request.query(&[("type", ticket_type.as_str())]);
-        }
-        if let Some(ref priority) = filters.priority {
-            request = request.query(&[("priority", priority.as_str())]);
-        }
-        if let Some(archived) = filters.archived {
-            request = request.query(&[("archived", &archived.to_string())]);
-        }
-        if let Some(is_spam) = filters.is_spam {
-            request = request.query(&[("isSpam", &is_spam.to_string())]);
-        }
-        if let Some(ref sort) = filters.sort {
-            request = request.query(&[("sort", sort.as_str())]);
-        }
-        if let Some(limit) = filters.limit {
-            request = request.query(&[("limit", &limit.to_string())]);
-        }
-        if let Some(skip) = filters.skip {
-            request = request.query(&[("skip", &skip.to_string())]);
-        }
-
+        let request = with_filters(self.client.get("/tickets"), filters);
         self.client.send_and_parse(request).await
     }
 
+    /// Walk all tickets matching the filters, page by page. `filters.skip`
+    /// is the starting offset and `filters.limit` the page size.
+    pub fn paginate(&self, filters: &TicketFilters) -> Paginator<'a, TicketFilters> {
+        Paginator::new(
+            self.client,
+            filters.clone(),
+            filters.skip.unwrap_or(0),
+            filters.limit.unwrap_or(DEFAULT_PAGE_SIZE),
+        )
+    }
+
     /// Get a single ticket by ID.
     pub async fn get(&self, ticket_id: &str) -> Result<Ticket, AppError> {
         let request = self.client.get(&format!("/tickets/{}", ticket_id));
         self.client.send_and_parse(request).await
     }
 
-    /// Update a ticket by ID. Accepts arbitrary JSON fields.
+    /// Update a ticket by ID, sending only the fields set in `update`.
     pub async fn update(
         &self,
         ticket_id: &str,
-        fields: serde_json::Value,
+        update: &UpdateTicketRequest,
     ) -> Result<Ticket, AppError> {
         let request = self
             .client
-            .put(&format!("/tickets/{}", ticket_id))
-            .json(&fields);
+            .patch(&format!("/tickets/{}", ticket_id))
+            .json(update);
         self.client.send_and_parse(request).await
     }
 
     /// Create a new ticket.
-    pub async fn create(&self, fields: serde_json::Value) -> Result<Ticket, AppError> {
-        let request = self.client.post("/tickets").json(&fields);
+    pub async fn create(&self, ticket: &CreateTicketRequest) -> Result<Ticket, AppError> {
+        let request = self.client.post("/tickets").json(ticket);
         self.client.send_and_parse(request).await
     }
 
-    /// Full-text search tickets.
-    pub async fn search(&self, query: &str) -> Result<serde_json::Value, AppError> {
+    /// Full-text search tickets, narrowed by the same filters as [`list`](Self::list).
+    pub async fn search(&self, search: &TicketSearch) -> Result<TicketListResponse, AppError> {
         let request = self
             .client
             .get("/tickets/search")
-            .query(&[("searchTerm", query)]);
+            .query(&[("query", search.query.as_str())]);
+        let request = with_filters(request, &search.filters);
 
         self.client.send_and_parse(request).await
     }
 
+    /// Walk all search results, page by page (see [`paginate`](Self::paginate)).
+    pub fn paginate_search(&self, search: &TicketSearch) -> Paginator<'a, TicketSearch> {
+        Paginator::new(
+            self.client,
+            search.clone(),
+            search.filters.skip.unwrap_or(0),
+            search.filters.limit.unwrap_or(DEFAULT_PAGE_SIZE),
+        )
+    }
+
     /// Get activity logs for a ticket.
-    pub async fn activity_logs(&self, ticket_id: &str) -> Result<serde_json::Value, AppError> {
+    pub async fn activity_logs(&self, ticket_id: &str) -> Result<Vec<ActivityLog>, AppError> {
         let request = self
             .client
-            .get(&format!("/tickets/{}/activity-logs", ticket_id));
-        self.client.send_and_parse(request).await
+            .get(&format!("/tickets/{}/activitylogs", ticket_id));
+        let response = self.client.send_and_parse(request).await?;
+        Ok(logs::entries(response, &["activityLogs", "logs"])?)
     }
 
     /// Get console logs for a ticket.
-    pub async fn console_logs(&self, ticket_id: &str) -> Result<serde_json::Value, AppError> {
+    pub async fn console_logs(&self, ticket_id: &str) -> Result<Vec<ConsoleLog>, AppError> {
         let request = self
             .client
-            .get(&format!("/tickets/{}/console-logs", ticket_id));
-        self.client.send_and_parse(request).await
+            .get(&format!("/tickets/{}/consolelogs", ticket_id));
+        let response = self.client.send_and_parse(request).await?;
+        Ok(logs::entries(
+            response,
+            &["consoleLogs", "consoleLog", "logs"],
+        )?)
     }
 
     /// Get network logs for a ticket.
-    pub async fn network_logs(&self, ticket_id: &str) -> Result<serde_json::Value, AppError> {
+    pub async fn network_logs(&self, ticket_id: &str) -> Result<Vec<NetworkLog>, AppError> {
         let request = self
             .client
-            .get(&format!("/tickets/{}/network-logs", ticket_id));
-        self.client.send_and_parse(request).await
+            .get(&format!("/tickets/{}/networklogs", ticket_id));
+        let response = self.client.send_and_parse(request).await?;
+        Ok(logs::entries(response, &["networkLogs", "logs"])?)
+    }
+}
+
+/// Add the filter, sort and paging query parameters shared by list and search.
+fn with_filters(
+    mut request: reqwest::RequestBuilder,
+    filters: &TicketFilters,
+) -> reqwest::RequestBuilder {
+    if !filters.status.is_empty() {
+        request = request.query(&[("status", join(&filters.status))]);
+    }
+    if !filters.ticket_type.is_empty() {
+        request = request.query(&[("type", join(&filters.ticket_type))]);
+    }
+    if !filters.priority.is_empty() {
+        request = request.query(&[("priority", join(&filters.priority))]);
+    }
+    if let Some(archived) = filters.archived {
+        request = request.query(&[("archived", &archived.to_string())]);
+    }
+    if let Some(is_spam) = filters.is_spam {
+        request = request.query(&[("isSpam", &is_spam.to_string())]);
     }
+    if let Some(ref sort) = filters.sort {
+        request = request.query(&[("sort", sort.as_str())]);
+    }
+    if let Some(limit) = filters.limit {
+        request = request.query(&[("limit", &limit.to_string())]);
+    }
+    if let Some(skip) = filters.skip {
+        request = request.query(&[("skip", &skip.to_string())]);
+    }
+    request
+}
+
+/// Multi-value filters are sent comma-separated (`status=OPEN,INPROGRESS`).
+fn join<T: fmt::Display>(values: &[FilterValue<T>]) -> String {
+    values
+        .iter()
+        .map(ToString::to_string)
+        .collect::<Vec<_>>()
+        .join(",")
 }
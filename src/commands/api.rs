@@ -0,0 +1,87 @@
+use std::io::Read;
+
+use reqwest::Method;
+use serde_json::Value;
+
+use gleap::client::{GleapClient, RawRequest};
+use gleap::error::AppError;
+use gleap::models::fields::infer_value;
+
+use crate::cli::api::ApiArgs;
+use crate::output::Output;
+
+/// Page size for --paginate when no `limit` query parameter is given.
+const DEFAULT_PAGE_SIZE: u64 = 100;
+
+pub async fn run(client: &GleapClient, output: &Output, args: ApiArgs) -> Result<(), AppError> {
+    let mut request = RawRequest::new(args.method, &args.path);
+    request.query = args.query;
+    if let Some(input) = &args.input {
+        request.body = Some(read_body(input)?);
+    } else if !args.fields.is_empty() || !args.typed_fields.is_empty() {
+        if request.method == Method::GET {
+            request.query.extend(args.fields);
+            request.query.extend(args.typed_fields);
+        } else {
+            let strings = args
+                .fields
+                .into_iter()
+                .map(|(key, value)| (key, Value::String(value)));
+            let typed = args
+                .typed_fields
+                .into_iter()
+                .map(|(key, value)| (key, infer_value(&value)));
+            request.body = Some(Value::Object(strings.chain(typed).collect()));
+        }
+    }
+
+    if !args.paginate {
+        let response = client.raw().send(&request).await?;
+        return output.json(&response);
+    }
+
+    if request.method != Method::GET {
+        return Err(AppError::Config("--paginate only works with GET".into()));
+    }
+    let number = |key: &str| -> Result<Option<u64>, AppError> {
+        request
+            .query
+            .iter()
+            .find(|(k, _)| k == key)
+            .map(|(_, value)| {
+                value
+                    .parse()
+                    .map_err(|_| AppError::Config(format!("invalid {key} '{value}'")))
+            })
+            .transpose()
+    };
+    let skip = number("skip")?.unwrap_or(0);
+    let page_size = number("limit")?.unwrap_or(DEFAULT_PAGE_SIZE);
+    let mut pages = client.raw().paginate(&request, skip, page_size);
+
+    let mut items = Vec::new();
+    while let Some(page) = pages.next_page().await? {
+        if output.streams() {
+            output.lines(&page)?;
+        } else {
+            items.extend(page);
+        }
+    }
+    if output.streams() {
+        return Ok(());
+    }
+
+    output.json(&items)
+}
+
+fn read_body(path: &std::path::Path) -> Result<Value, AppError> {
+    let text = if path.as_os_str() == "-" {
+        let mut text = String::new();
+        std::io::stdin().read_to_string(&mut text)?;
+        text
+    } else {
+        std::fs::read_to_string(path)?
+    };
+    serde_json::from_str(&text)
+        .map_err(|e| AppError::Config(format!("{} is not valid JSON: {e}", path.display())))
+}
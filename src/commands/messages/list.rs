@@ -2,21 +2,54 @@ use gleap::client::GleapClient;
 use gleap::error::AppError;
 use gleap::models::message::MessageFilters;
 
-pub async fn run(
-    client: &GleapClient,
-    ticket: &str,
-    limit: u64,
-    skip: u64,
-) -> Result<(), AppError> {
+use crate::cli::messages::ListArgs;
+use crate::output::Output;
+
+pub async fn run(client: &GleapClient, output: &Output, args: ListArgs) -> Result<(), AppError> {
+    let pagination = args.pagination;
+    let bot = match (args.bot, args.no_bot) {
+        (true, _) => Some(true),
+        (_, true) => Some(false),
+        _ => None,
+    };
     let filters = MessageFilters {
-        ticket: Some(ticket.to_string()),
-        limit: Some(limit),
-        skip: Some(skip),
-        ..Default::default()
+        ticket: args.ticket,
+        message_type: (!args.message_type.is_empty()).then(|| {
+            args.message_type
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>()
+                .join(",")
+        }),
+        bot,
+        since: args.since,
+        until: args.until,
+        language: args.translate,
+        limit: Some(pagination.limit),
+        skip: Some(pagination.skip),
     };
 
-    let messages = client.messages().list(&filters).await?;
-    let json = serde_json::to_string_pretty(&messages)?;
-    println!("{}", json);
-    Ok(())
+    if !pagination.all && pagination.max.is_none() {
+        let messages = client.messages().list(&filters).await?;
+        return output.messages(&messages);
+    }
+
+    let mut pages = client.messages().paginate(&filters);
+    if let Some(max) = pagination.max {
+        pages = pages.max_items(max);
+    }
+
+    let mut messages = Vec::new();
+    while let Some(page) = pages.next_page().await? {
+        if output.streams() {
+            output.lines(&page)?;
+        } else {
+            messages.extend(page);
+        }
+    }
+    if output.streams() {
+        return Ok(());
+    }
+
+    output.messages(&messages)
 }
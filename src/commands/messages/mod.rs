@@ -1,3 +1,19 @@
 pub mod list;
 pub mod note;
 pub mod reply;
+
+use gleap::render::markdown;
+
+use crate::cli::messages::TextFormat;
+
+/// Build the `comment` payload for a note or reply. Without a flag, Markdown
+/// formatting is detected and converted; unformatted text is sent as-is.
+fn comment(text: &str, format: &TextFormat) -> serde_json::Value {
+    if format.plain {
+        serde_json::Value::String(text.to_string())
+    } else if format.markdown {
+        markdown::to_doc(text)
+    } else {
+        markdown::to_comment(text)
+    }
+}
@@ -1,9 +1,19 @@
 use gleap::client::GleapClient;
 use gleap::error::AppError;
 
-pub async fn run(client: &GleapClient, ticket: &str, text: &str) -> Result<(), AppError> {
-    let message = client.messages().create_comment(ticket, text).await?;
-    let json = serde_json::to_string_pretty(&message)?;
-    println!("{}", json);
-    Ok(())
+use crate::cli::messages::TextFormat;
+use crate::output::Output;
+
+pub async fn run(
+    client: &GleapClient,
+    output: &Output,
+    ticket: &str,
+    text: &str,
+    text_format: &TextFormat,
+) -> Result<(), AppError> {
+    let message = client
+        .messages()
+        .create_comment(ticket, super::comment(text, text_format))
+        .await?;
+    output.json(&message)
 }
@@ -1,9 +1,40 @@
 use gleap::client::GleapClient;
 use gleap::error::AppError;
+use gleap::models::logs::ConsoleFilter;
 
-pub async fn run(client: &GleapClient, id: &str) -> Result<(), AppError> {
-    let logs = client.tickets().console_logs(id).await?;
-    let json = serde_json::to_string_pretty(&logs)?;
-    println!("{}", json);
-    Ok(())
+use crate::cli::tickets::{ConsoleArgs, LogTime};
+use crate::output::Output;
+
+pub async fn run(client: &GleapClient, output: &Output, args: ConsoleArgs) -> Result<(), AppError> {
+    let logs = client.tickets().console_logs(&args.id).await?;
+
+    // Durations in --since/--until count from when the ticket was reported.
+    let relative = [args.since, args.until]
+        .iter()
+        .any(|time| matches!(time, Some(LogTime::FromReport(_))));
+    let reported_at = if relative {
+        let ticket = client.tickets().get(&args.id).await?;
+        let reported_at = ticket
+            .created_at
+            .as_deref()
+            .and_then(|ts| chrono::DateTime::parse_from_rfc3339(ts).ok())
+            .map(|at| at.to_utc());
+        if reported_at.is_none() {
+            return Err(AppError::Config(
+                "ticket has no report time; use RFC 3339 timestamps for --since/--until".into(),
+            ));
+        }
+        reported_at
+    } else {
+        None
+    };
+
+    let filter = ConsoleFilter {
+        levels: args.level,
+        pattern: args.grep,
+        since: args.since.and_then(|t| t.resolve(reported_at)),
+        until: args.until.and_then(|t| t.resolve(reported_at)),
+        tail: args.tail,
+    };
+    output.console_logs(&filter.apply(logs), args.stack)
 }
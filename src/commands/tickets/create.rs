@@ -1,51 +1,32 @@
 use gleap::client::GleapClient;
 use gleap::error::AppError;
+use gleap::models::fields::{self, DataField};
+use gleap::models::ticket::CreateTicketRequest;
 
-pub async fn run(
-    client: &GleapClient,
-    title: &str,
-    ticket_type: Option<String>,
-    status: Option<String>,
-    priority: Option<String>,
-    description: Option<String>,
-    tags: Option<String>,
-) -> Result<(), AppError> {
-    let mut fields = serde_json::Map::new();
+use crate::cli::tickets::CreateArgs;
+use crate::output::Output;
 
-    fields.insert("title".into(), serde_json::Value::String(title.into()));
-
-    if let Some(ticket_type) = ticket_type {
-        fields.insert("type".into(), serde_json::Value::String(ticket_type));
-    }
-    if let Some(status) = status {
-        fields.insert("status".into(), serde_json::Value::String(status));
-    }
-    if let Some(priority) = priority {
-        fields.insert("priority".into(), serde_json::Value::String(priority));
-    }
-    if let Some(description) = description {
-        fields.insert("description".into(), serde_json::Value::String(description));
-    }
+use super::update::split_tags;
 
+pub async fn run(client: &GleapClient, output: &Output, args: CreateArgs) -> Result<(), AppError> {
     // Always include "gleap-cli" tag, plus any user-provided tags
-    let mut tag_list: Vec<serde_json::Value> = tags
-        .iter()
-        .flat_map(|t| t.split(','))
-        .map(|t| serde_json::Value::String(t.trim().to_string()))
-        .filter(|t| t.as_str() != Some(""))
-        .collect();
-
-    if !tag_list.iter().any(|t| t.as_str() == Some("gleap-cli")) {
-        tag_list.push(serde_json::Value::String("gleap-cli".into()));
+    let mut tags = args.tags.as_deref().map(split_tags).unwrap_or_default();
+    if !tags.iter().any(|t| t == "gleap-cli") {
+        tags.push("gleap-cli".into());
     }
 
-    fields.insert("tags".into(), serde_json::Value::Array(tag_list));
+    let request = CreateTicketRequest {
+        title: args.title,
+        ticket_type: args.ticket_type,
+        status: args.status,
+        priority: args.priority,
+        description: args.description,
+        tags: Some(tags),
+        custom_data: fields::apply_edits(None, DataField::CustomData, &args.set)?,
+        form_data: fields::apply_edits(None, DataField::FormData, &args.set)?,
+        ..Default::default()
+    };
 
-    let ticket = client
-        .tickets()
-        .create(serde_json::Value::Object(fields))
-        .await?;
-    let json = serde_json::to_string_pretty(&ticket)?;
-    println!("{}", json);
-    Ok(())
+    let ticket = client.tickets().create(&request).await?;
+    output.json(&ticket)
 }
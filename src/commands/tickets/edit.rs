@@ -0,0 +1,325 @@
+use std::collections::BTreeSet;
+use std::fs::{File, OpenOptions};
+use std::io::{BufRead, IsTerminal, Write};
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use gleap::client::GleapClient;
+use gleap::error::AppError;
+use gleap::models::ticket::{Ticket, TicketPriority, TicketStatus, UpdateTicketRequest};
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+use crate::output::Output;
+
+/// The fields of a ticket offered for editing, in the order they appear in
+/// the file.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct EditableTicket {
+    title: Option<String>,
+    status: Option<TicketStatus>,
+    priority: Option<TicketPriority>,
+    tags: Vec<String>,
+    description: Option<String>,
+    #[serde(rename = "customData")]
+    custom_data: Map<String, Value>,
+}
+
+impl From<&Ticket> for EditableTicket {
+    fn from(ticket: &Ticket) -> Self {
+        Self {
+            title: ticket.title.clone(),
+            status: ticket.status,
+            priority: ticket.priority,
+            tags: ticket.tags.clone().unwrap_or_default(),
+            description: ticket.description.clone(),
+            custom_data: match &ticket.custom_data {
+                Some(Value::Object(map)) => map.clone(),
+                _ => Map::new(),
+            },
+        }
+    }
+}
+
+pub async fn run(
+    client: &GleapClient,
+    output: &Output,
+    id: &str,
+    yes: bool,
+) -> Result<(), AppError> {
+    let ticket = client.tickets().get(id).await?;
+    let original = EditableTicket::from(&ticket);
+
+    let (path, mut file) = create_temp_file(id)?;
+    let written = file
+        .write_all((serde_json::to_string_pretty(&original)? + "\n").as_bytes())
+        .map_err(AppError::from);
+    drop(file);
+    if let Err(e) = written.and_then(|_| open_editor(&path)) {
+        let _ = std::fs::remove_file(&path);
+        return Err(e);
+    }
+
+    // Keep the file when it can't be used, so the edits aren't lost.
+    let contents = std::fs::read_to_string(&path)?;
+    let edited = parse(&contents, &original).map_err(|e| {
+        AppError::Config(format!("{e} (your edits are saved in {})", path.display()))
+    })?;
+    std::fs::remove_file(&path)?;
+
+    let update = changes(&original, &edited);
+    if update.is_empty() {
+        eprintln!("No changes.");
+        return Ok(());
+    }
+    for line in diff(&original, &edited)? {
+        eprintln!("{line}");
+    }
+    if !yes && !confirm("Apply these changes?")? {
+        eprintln!("Aborted.");
+        return Ok(());
+    }
+
+    let ticket = client.tickets().update(id, &update).await?;
+    output.json(&ticket)
+}
+
+/// Create a new file only the current user can read, so the customer data
+/// in it isn't exposed in a shared temp directory. Fails rather than follow
+/// a file or symlink that's already there.
+fn create_temp_file(id: &str) -> Result<(PathBuf, File), AppError> {
+    let id: String = id
+        .chars()
+        .filter(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_'))
+        .take(64)
+        .collect();
+    let file_name = format!("gleap-ticket-{id}-{}.json", std::process::id());
+    let path = std::env::temp_dir().join(file_name);
+
+    let mut options = OpenOptions::new();
+    options.write(true).create_new(true);
+    #[cfg(unix)]
+    std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+    let file = options.open(&path)?;
+    Ok((path, file))
+}
+
+/// Run `$VISUAL` or `$EDITOR` (falling back to `vi`) on the file and wait for
+/// it to exit. The variable may include arguments, e.g. `code --wait`.
+fn open_editor(path: &Path) -> Result<(), AppError> {
+    let editor = std::env::var("VISUAL")
+        .or_else(|_| std::env::var("EDITOR"))
+        .ok()
+        .filter(|editor| !editor.trim().is_empty())
+        .unwrap_or_else(|| "vi".into());
+    let mut words = editor.split_whitespace();
+    let program = words.next().expect("editor is not blank");
+
+    let status = Command::new(program).args(words).arg(path).status()?;
+    if !status.success() {
+        return Err(AppError::Config(format!(
+            "editor '{editor}' exited with {status}; ticket left unchanged"
+        )));
+    }
+    Ok(())
+}
+
+/// Parse the edited file. Status and priority must be values the CLI knows,
+/// unless they were left as they were, and can't be cleared.
+fn parse(contents: &str, original: &EditableTicket) -> Result<EditableTicket, String> {
+    let edited: EditableTicket =
+        serde_json::from_str(contents).map_err(|e| format!("invalid ticket JSON: {e}"))?;
+    if edited.status != original.status {
+        match edited.status {
+            None => return Err("status can't be cleared".into()),
+            Some(TicketStatus::Unknown) => {
+                return Err("unknown status (expected OPEN, INPROGRESS or DONE)".into());
+            }
+            Some(_) => {}
+        }
+    }
+    if edited.priority != original.priority {
+        match edited.priority {
+            None => return Err("priority can't be cleared".into()),
+            Some(TicketPriority::Unknown) => {
+                return Err("unknown priority (expected LOW, MEDIUM or HIGH)".into());
+            }
+            Some(_) => {}
+        }
+    }
+    Ok(edited)
+}
+
+/// Describe each changed field as `-`/`+` lines. Custom data is compared key
+/// by key so only the values that changed are listed.
+fn diff(original: &EditableTicket, edited: &EditableTicket) -> Result<Vec<String>, AppError> {
+    let mut lines = Vec::new();
+    diff_values(
+        "",
+        &serde_json::to_value(original)?,
+        &serde_json::to_value(edited)?,
+        &mut lines,
+    );
+    Ok(lines)
+}
+
+fn diff_values(path: &str, old: &Value, new: &Value, lines: &mut Vec<String>) {
+    if old == new {
+        return;
+    }
+    if let (Value::Object(old), Value::Object(new)) = (old, new) {
+        let keys: BTreeSet<&String> = old.keys().chain(new.keys()).collect();
+        for key in keys {
+            diff_values(
+                &child_path(path, key),
+                old.get(key).unwrap_or(&Value::Null),
+                new.get(key).unwrap_or(&Value::Null),
+                lines,
+            );
+        }
+        return;
+    }
+    lines.push(format!("{path}:"));
+    if !old.is_null() {
+        lines.push(format!("  - {old}"));
+    }
+    if !new.is_null() {
+        lines.push(format!("  + {new}"));
+    }
+}
+
+fn child_path(path: &str, key: &str) -> String {
+    if path.is_empty() {
+        key.to_string()
+    } else {
+        format!("{path}.{key}")
+    }
+}
+
+/// Build an update holding only the fields that differ. Cleared text fields
+/// are sent as empty strings; [`parse`] has already rejected a cleared status
+/// or priority.
+fn changes(original: &EditableTicket, edited: &EditableTicket) -> UpdateTicketRequest {
+    fn text(old: &Option<String>, new: &Option<String>) -> Option<String> {
+        (old != new).then(|| new.clone().unwrap_or_default())
+    }
+
+    UpdateTicketRequest {
+        title: text(&original.title, &edited.title),
+        status: edited.status.filter(|_| edited.status != original.status),
+        priority: edited
+            .priority
+            .filter(|_| edited.priority != original.priority),
+        tags: (edited.tags != original.tags).then(|| edited.tags.clone()),
+        description: text(&original.description, &edited.description),
+        custom_data: (edited.custom_data != original.custom_data)
+            .then(|| edited.custom_data.clone()),
+        ..Default::default()
+    }
+}
+
+/// Ask a yes/no question on stderr. Anything but `y`/`yes` counts as no.
+fn confirm(question: &str) -> Result<bool, AppError> {
+    if !std::io::stdin().is_terminal() {
+        return Err(AppError::Config(
+            "cannot ask for confirmation without a terminal; pass --yes to apply".into(),
+        ));
+    }
+    eprint!("{question} [y/N] ");
+    std::io::stderr().flush()?;
+    let mut answer = String::new();
+    std::io::stdin().lock().read_line(&mut answer)?;
+    Ok(matches!(
+        answer.trim().to_ascii_lowercase().as_str(),
+        "y" | "yes"
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn ticket() -> EditableTicket {
+        let ticket: Ticket = serde_json::from_value(json!({
+            "id": "t1",
+            "title": "Login broken",
+            "status": "OPEN",
+            "tags": ["ui"],
+            "description": "Safari only",
+            "customData": { "plan": "free", "account": { "id": 42 } }
+        }))
+        .unwrap();
+        EditableTicket::from(&ticket)
+    }
+
+    #[test]
+    fn test_only_changed_fields_are_sent() {
+        let original = ticket();
+        let mut edited = original.clone();
+        edited.status = Some(TicketStatus::Done);
+        edited.description = None;
+        edited.custom_data.insert("plan".into(), json!("pro"));
+
+        let update = serde_json::to_value(changes(&original, &edited)).unwrap();
+        assert_eq!(
+            update,
+            json!({
+                "status": "DONE",
+                "description": "",
+                "customData": { "plan": "pro", "account": { "id": 42 } }
+            })
+        );
+        assert!(changes(&original, &original).is_empty());
+    }
+
+    #[test]
+    fn test_diff_lists_nested_changes() {
+        let original = ticket();
+        let mut edited = original.clone();
+        edited.title = Some("Login broken on Safari".into());
+        edited.custom_data.remove("account");
+        edited.custom_data.insert("linkedPr".into(), json!(1234));
+
+        assert_eq!(
+            diff(&original, &edited).unwrap(),
+            vec![
+                "customData.account:",
+                "  - {\"id\":42}",
+                "customData.linkedPr:",
+                "  + 1234",
+                "title:",
+                "  - \"Login broken\"",
+                "  + \"Login broken on Safari\"",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_rejects_unknown_values() {
+        let original = ticket();
+        let mut value = serde_json::to_value(&original).unwrap();
+        value["status"] = json!("CLOSED");
+        assert!(parse(&value.to_string(), &original).is_err());
+
+        value["status"] = json!(null);
+        assert!(parse(&value.to_string(), &original).is_err());
+
+        value["status"] = json!("DONE");
+        value["assignee"] = json!("ann");
+        assert!(parse(&value.to_string(), &original).is_err());
+    }
+
+    #[test]
+    fn test_parse_keeps_untouched_unknown_values() {
+        let mut original = ticket();
+        original.status = Some(TicketStatus::Unknown);
+        let mut value = serde_json::to_value(&original).unwrap();
+        value["title"] = json!("Renamed");
+
+        let edited = parse(&value.to_string(), &original).unwrap();
+        let update = serde_json::to_value(changes(&original, &edited)).unwrap();
+        assert_eq!(update, json!({ "title": "Renamed" }));
+    }
+}
@@ -1,29 +1,74 @@
-use gleap::client::GleapClient;
+use gleap::client::{GleapClient, PageQuery, Paginator};
 use gleap::error::AppError;
-use gleap::models::ticket::TicketFilters;
+use gleap::models::ticket::{Ticket, TicketFilters, TicketListResponse};
 
-pub async fn run(
-    client: &GleapClient,
-    status: Option<String>,
-    ticket_type: Option<String>,
-    priority: Option<String>,
-    sort: String,
-    limit: u64,
-    skip: u64,
-) -> Result<(), AppError> {
+use crate::cli::shared::Pagination;
+use crate::cli::tickets::{FilterArgs, ListArgs};
+use crate::output::Output;
+
+pub async fn run(client: &GleapClient, output: &Output, args: ListArgs) -> Result<(), AppError> {
     let filters = TicketFilters {
-        status,
-        ticket_type,
-        priority,
-        sort: Some(sort),
-        limit: Some(limit),
-        skip: Some(skip),
-        archived: Some(false),
-        is_spam: Some(false),
+        archived: args.archived,
+        is_spam: args.spam,
+        ..filters(args.filters, Some(args.sort), &args.pagination)
     };
 
-    let response = client.tickets().list(&filters).await?;
-    let json = serde_json::to_string_pretty(&response)?;
-    println!("{}", json);
-    Ok(())
+    if !args.pagination.all && args.pagination.max.is_none() {
+        let response = client.tickets().list(&filters).await?;
+        return output.tickets(&response);
+    }
+    print_pages(
+        client.tickets().paginate(&filters),
+        output,
+        &args.pagination,
+    )
+    .await
+}
+
+/// Build API filters from the shared filter flags.
+pub(crate) fn filters(
+    args: FilterArgs,
+    sort: Option<String>,
+    pagination: &Pagination,
+) -> TicketFilters {
+    TicketFilters {
+        status: args.status,
+        ticket_type: args.ticket_type,
+        priority: args.priority,
+        sort,
+        limit: Some(pagination.limit),
+        skip: Some(pagination.skip),
+        archived: None,
+        is_spam: None,
+    }
+}
+
+/// Walk pages for `--all`/`--max`, streaming them for JSON Lines and
+/// collecting them for every other format.
+pub(crate) async fn print_pages<Q: PageQuery<Item = Ticket>>(
+    mut pages: Paginator<'_, Q>,
+    output: &Output,
+    pagination: &Pagination,
+) -> Result<(), AppError> {
+    if let Some(max) = pagination.max {
+        pages = pages.max_items(max);
+    }
+
+    let mut tickets = Vec::new();
+    while let Some(page) = pages.next_page().await? {
+        if output.streams() {
+            output.lines(&page)?;
+        } else {
+            tickets.extend(page);
+        }
+    }
+    if output.streams() {
+        return Ok(());
+    }
+
+    output.tickets(&TicketListResponse {
+        count: Some(tickets.len() as u64),
+        total_count: pages.total_count(),
+        tickets,
+    })
 }
@@ -1,8 +1,11 @@
 pub mod activity_logs;
 pub mod console_logs;
 pub mod create;
+pub mod edit;
 pub mod get;
 pub mod list;
 pub mod network_logs;
 pub mod search;
+pub mod timeline;
+pub mod transcript;
 pub mod update;
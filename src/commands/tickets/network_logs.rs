@@ -1,9 +1,66 @@
 use gleap::client::GleapClient;
+use gleap::client::Redaction;
 use gleap::error::AppError;
+use gleap::models::logs::{NetworkFilter, NetworkLog};
+use gleap::render::curl;
 
-pub async fn run(client: &GleapClient, id: &str) -> Result<(), AppError> {
-    let logs = client.tickets().network_logs(id).await?;
-    let json = serde_json::to_string_pretty(&logs)?;
-    println!("{}", json);
+use crate::cli::tickets::NetworkArgs;
+use crate::output::Output;
+
+pub async fn run(client: &GleapClient, output: &Output, args: NetworkArgs) -> Result<(), AppError> {
+    let logs = client.tickets().network_logs(&args.id).await?;
+    let filter = NetworkFilter {
+        statuses: args.status,
+        methods: args.method,
+        url_glob: args.url_glob,
+        slower_than: args.slower_than,
+    };
+    let logs = filter.apply(logs);
+
+    let redaction = Redaction::new(&args.redact_header);
+    if args.as_curl {
+        return print_curl(&logs, args.index, &redaction);
+    }
+    output.network_logs(&logs, &redaction)
+}
+
+/// Print one request as a bare command, or all of them as a script with
+/// each command headed by its summary number.
+fn print_curl(
+    logs: &[NetworkLog],
+    index: Option<usize>,
+    redaction: &Redaction,
+) -> Result<(), AppError> {
+    if let Some(index) = index {
+        let log = logs.get(index).ok_or_else(|| {
+            AppError::Config(format!(
+                "no request #{index}: {} matched (numbered from 0)",
+                logs.len()
+            ))
+        })?;
+        println!("{}", curl::to_curl(log, redaction));
+        return Ok(());
+    }
+
+    if logs.is_empty() {
+        eprintln!("No network logs found.");
+        return Ok(());
+    }
+    for (index, log) in logs.iter().enumerate() {
+        if index > 0 {
+            println!();
+        }
+        let status = log
+            .status()
+            .map(|status| status.to_string())
+            .unwrap_or_else(|| "-".into());
+        let summary = format!(
+            "[{index}] {} {} → {status}",
+            log.method.as_deref().unwrap_or("GET"),
+            log.url.as_deref().unwrap_or("")
+        );
+        println!("{}", curl::comment(&summary));
+        println!("{}", curl::to_curl(log, redaction));
+    }
     Ok(())
 }
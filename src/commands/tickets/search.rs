@@ -1,9 +1,26 @@
 use gleap::client::GleapClient;
 use gleap::error::AppError;
+use gleap::models::ticket::TicketSearch;
 
-pub async fn run(client: &GleapClient, query: &str) -> Result<(), AppError> {
-    let response = client.tickets().search(query).await?;
-    let json = serde_json::to_string_pretty(&response)?;
-    println!("{}", json);
-    Ok(())
+use crate::cli::tickets::SearchArgs;
+use crate::output::Output;
+
+use super::list::{filters, print_pages};
+
+pub async fn run(client: &GleapClient, output: &Output, args: SearchArgs) -> Result<(), AppError> {
+    let search = TicketSearch {
+        query: args.query,
+        filters: filters(args.filters, args.sort, &args.pagination),
+    };
+
+    if !args.pagination.all && args.pagination.max.is_none() {
+        let response = client.tickets().search(&search).await?;
+        return output.tickets(&response);
+    }
+    print_pages(
+        client.tickets().paginate_search(&search),
+        output,
+        &args.pagination,
+    )
+    .await
 }
@@ -0,0 +1,20 @@
+use gleap::client::GleapClient;
+use gleap::error::AppError;
+use gleap::models::message::MessageFilters;
+use gleap::render::timeline::Timeline;
+
+use crate::output::Output;
+
+pub async fn run(client: &GleapClient, output: &Output, id: &str) -> Result<(), AppError> {
+    let ticket = client.tickets().get(id).await?;
+    let activity = client.tickets().activity_logs(id).await?;
+
+    let filters = MessageFilters {
+        ticket: Some(id.to_string()),
+        ..Default::default()
+    };
+    let messages = client.messages().paginate(&filters).collect_all().await?;
+
+    let timeline = Timeline::new(&ticket, &activity, &messages, chrono::Utc::now());
+    output.timeline(&timeline)
+}
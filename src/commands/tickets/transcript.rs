@@ -0,0 +1,24 @@
+use gleap::client::GleapClient;
+use gleap::error::AppError;
+use gleap::models::message::MessageFilters;
+use gleap::render::transcript::Transcript;
+
+use crate::output::Output;
+
+pub async fn run(
+    client: &GleapClient,
+    output: &Output,
+    id: &str,
+    exclude_notes: bool,
+) -> Result<(), AppError> {
+    let ticket = client.tickets().get(id).await?;
+
+    let filters = MessageFilters {
+        ticket: Some(id.to_string()),
+        ..Default::default()
+    };
+    let messages = client.messages().paginate(&filters).collect_all().await?;
+
+    let transcript = Transcript::new(&ticket, &messages, !exclude_notes);
+    output.transcript(&transcript)
+}
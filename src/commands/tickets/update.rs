@@ -1,36 +1,100 @@
 use gleap::client::GleapClient;
 use gleap::error::AppError;
+use gleap::models::fields::{self, DataField};
+use gleap::models::ticket::UpdateTicketRequest;
 
-pub async fn run(
-    client: &GleapClient,
-    id: &str,
-    status: Option<String>,
-    priority: Option<String>,
-    title: Option<String>,
-) -> Result<(), AppError> {
-    let mut fields = serde_json::Map::new();
-
-    if let Some(status) = status {
-        fields.insert("status".into(), serde_json::Value::String(status));
+use crate::cli::tickets::UpdateArgs;
+use crate::output::Output;
+
+pub async fn run(client: &GleapClient, output: &Output, args: UpdateArgs) -> Result<(), AppError> {
+    let mut update = UpdateTicketRequest {
+        title: args.title,
+        status: args.status,
+        priority: args.priority,
+        ticket_type: args.ticket_type,
+        description: args.description,
+        processing_team: args.team,
+        archived: args.archived,
+        is_spam: args.spam,
+        ..Default::default()
+    };
+
+    if let Some(assignee) = args.assignee {
+        update.processing_user = Some(Some(assignee));
+    } else if args.unassign {
+        update.processing_user = Some(None);
     }
-    if let Some(priority) = priority {
-        fields.insert("priority".into(), serde_json::Value::String(priority));
+
+    // Unsets run first so `--unset a --set a.b=1` replaces `a`.
+    let mut edits = args.unset;
+    edits.extend(args.set);
+    let edit_tags = args.tags.is_none() && !(args.add_tag.is_empty() && args.remove_tag.is_empty());
+
+    // Tags, customData and formData are replaced wholesale by the API, so
+    // incremental edits are applied to the ticket's current values.
+    let current = if edit_tags || !edits.is_empty() {
+        Some(client.tickets().get(&args.id).await?)
+    } else {
+        None
+    };
+
+    if let Some(tags) = args.tags {
+        update.tags = Some(split_tags(&tags));
+    } else if let Some(current) = current.as_ref().filter(|_| edit_tags) {
+        let current_tags = current.tags.clone().unwrap_or_default();
+        let mut tags = current_tags.clone();
+        tags.retain(|tag| !args.remove_tag.contains(tag));
+        for tag in args.add_tag {
+            if !tags.contains(&tag) {
+                tags.push(tag);
+            }
+        }
+        // Adding a tag that's there or removing one that isn't changes nothing.
+        update.tags = (tags != current_tags).then_some(tags);
     }
-    if let Some(title) = title {
-        fields.insert("title".into(), serde_json::Value::String(title));
+
+    if let Some(custom_data) = args.custom_data {
+        match serde_json::from_str(&custom_data) {
+            Ok(serde_json::Value::Object(map)) => update.custom_data = Some(map),
+            _ => {
+                return Err(AppError::Config(
+                    "--custom-data must be a JSON object, e.g. '{\"plan\":\"pro\"}'".into(),
+                ));
+            }
+        }
     }
 
-    if fields.is_empty() {
+    if let Some(current) = &current {
+        let custom_data = update.custom_data.clone().map(serde_json::Value::Object);
+        let custom_data = custom_data.as_ref().or(current.custom_data.as_ref());
+        if let Some(data) = fields::apply_edits(custom_data, DataField::CustomData, &edits)? {
+            update.custom_data = Some(data);
+        }
+        let form_data = current.form_data.as_ref();
+        if let Some(data) = fields::apply_edits(form_data, DataField::FormData, &edits)? {
+            update.form_data = Some(data);
+        }
+    }
+
+    if update.is_empty() && current.is_some() {
+        eprintln!("Nothing to update: the ticket already has these values.");
+        return Ok(());
+    }
+    if update.is_empty() {
         return Err(AppError::Config(
-            "No fields to update. Provide --status, --priority, or --title.".into(),
+            "No fields to update. See `gleap tickets update --help` for the available flags."
+                .into(),
         ));
     }
 
-    let ticket = client
-        .tickets()
-        .update(id, serde_json::Value::Object(fields))
-        .await?;
-    let json = serde_json::to_string_pretty(&ticket)?;
-    println!("{}", json);
-    Ok(())
+    let ticket = client.tickets().update(&args.id, &update).await?;
+    output.json(&ticket)
+}
+
+/// Split a comma-separated tag list, dropping empty entries.
+pub(crate) fn split_tags(tags: &str) -> Vec<String> {
+    tags.split(',')
+        .map(|tag| tag.trim().to_string())
+        .filter(|tag| !tag.is_empty())
+        .collect()
 }
@@ -2,6 +2,7 @@ use crate::error::AppError;
 
 pub mod keychain;
 
+#[derive(Clone)]
 pub struct GleapConfig {
     pub api_key: String,
     pub project_id: String,
@@ -1,22 +1,35 @@
+use std::fmt;
 use std::io;
+
+use serde::Serialize;
+use serde_json::Value;
 use thiserror::Error;
 
+use crate::client::RequestPreview;
+use crate::models::ticket::{TicketPriority, TicketStatus, TicketType};
+
 #[derive(Error, Debug)]
 pub enum AppError {
-    #[error("Authentication failed: {0}")]
-    Auth(String),
+    #[error("Authentication failed: {body}")]
+    Auth {
+        status: u16,
+        body: Box<ApiErrorBody>,
+    },
 
     #[error("API error: {0}")]
     Api(String),
 
-    #[error("API error ({status}): {message}")]
-    ApiStatus { status: u16, message: String },
+    #[error("API error ({status}): {body}")]
+    ApiStatus {
+        status: u16,
+        body: Box<ApiErrorBody>,
+    },
 
     #[error("Configuration error: {0}")]
     Config(String),
 
     #[error("Not found: {0}")]
-    NotFound(String),
+    NotFound(Box<ApiErrorBody>),
 
     #[error("Rate limited: retry after {retry_after_secs}s")]
     RateLimited { retry_after_secs: u64 },
@@ -29,12 +42,32 @@ pub enum AppError {
 
     #[error("Serialization error: {0}")]
     Serialization(#[from] serde_json::Error),
+
+    /// A dry run stopped before sending this request.
+    #[error("Dry run: {} {} not sent", .0.method, .0.url)]
+    DryRun(Box<RequestPreview>),
 }
 
 impl AppError {
+    /// The process exit code for this error. These are a stable contract
+    /// for scripts and won't be reassigned:
+    ///
+    /// | Code | Kind            | Meaning                          |
+    /// |------|-----------------|----------------------------------|
+    /// | 0    |                 | Success                          |
+    /// | 2    | `auth`          | Authentication failure (401/403) |
+    /// | 3    | `api`           | Other API error                  |
+    /// | 4    | `config`        | Configuration or usage error     |
+    /// | 5    | `not_found`     | Not found (404)                  |
+    /// | 6    | `rate_limited`  | Rate limited (429)               |
+    /// | 7    | `http`          | Network or HTTP failure          |
+    /// | 8    | `io`            | IO error                         |
+    /// | 9    | `serialization` | Unexpected response or bad JSON  |
+    ///
+    /// A dry run stopping before a request (`dry_run`) is a success too.
     pub fn exit_code(&self) -> i32 {
         match self {
-            AppError::Auth(_) => 2,
+            AppError::Auth { .. } => 2,
             AppError::Api(_) => 3,
             AppError::ApiStatus { .. } => 3,
             AppError::Config(_) => 4,
@@ -43,6 +76,363 @@ impl AppError {
             AppError::Http(_) => 7,
             AppError::Io(_) => 8,
             AppError::Serialization(_) => 9,
+            AppError::DryRun(_) => 0,
+        }
+    }
+
+    /// A stable name for the kind of error (see [`exit_code`](Self::exit_code)).
+    pub fn kind(&self) -> &'static str {
+        match self {
+            AppError::Auth { .. } => "auth",
+            AppError::Api(_) | AppError::ApiStatus { .. } => "api",
+            AppError::Config(_) => "config",
+            AppError::NotFound(_) => "not_found",
+            AppError::RateLimited { .. } => "rate_limited",
+            AppError::Http(_) => "http",
+            AppError::Io(_) => "io",
+            AppError::Serialization(_) => "serialization",
+            AppError::DryRun(_) => "dry_run",
+        }
+    }
+
+    /// The HTTP status of the response that caused the error, if any.
+    pub fn status(&self) -> Option<u16> {
+        match self {
+            AppError::Auth { status, .. } | AppError::ApiStatus { status, .. } => Some(*status),
+            AppError::NotFound(_) => Some(404),
+            AppError::RateLimited { .. } => Some(429),
+            AppError::Http(e) => e.status().map(|status| status.as_u16()),
+            _ => None,
+        }
+    }
+
+    /// A machine-readable description of the error.
+    pub fn report(&self) -> ErrorReport {
+        ErrorReport {
+            kind: self.kind(),
+            exit_code: self.exit_code(),
+            status: self.status(),
+            message: self.to_string(),
+            retry_after: match self {
+                AppError::RateLimited { retry_after_secs } => Some(*retry_after_secs),
+                _ => None,
+            },
+            request: self.api_body().and_then(|body| body.request_id.clone()),
+            hint: self.hint(),
+        }
+    }
+
+    /// The parsed error response, for errors the API returned.
+    pub fn api_body(&self) -> Option<&ApiErrorBody> {
+        match self {
+            AppError::Auth { body, .. } | AppError::ApiStatus { body, .. } => Some(body),
+            AppError::NotFound(body) => Some(body),
+            _ => None,
+        }
+    }
+
+    /// A suggestion for fixing the error, if there's an obvious one.
+    pub fn hint(&self) -> Option<String> {
+        match self {
+            AppError::Auth { .. } => Some(
+                "check the API key and project ID, or run `gleap auth login` to store new ones"
+                    .into(),
+            ),
+            AppError::ApiStatus { body, .. } => body.hint(),
+            AppError::RateLimited { .. } => {
+                Some("lower --rate-limit to leave room for other clients on this project".into())
+            }
+            AppError::Serialization(_) => Some("use -vv to see the raw API response".into()),
+            _ => None,
         }
     }
 }
+
+/// An error as reported by `--error-format json`. Field names and `kind`
+/// values are stable.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ErrorReport {
+    pub kind: &'static str,
+    pub exit_code: i32,
+    /// HTTP status, for errors caused by a response.
+    pub status: Option<u16>,
+    pub message: String,
+    /// Seconds to wait before retrying, for `rate_limited` errors.
+    pub retry_after: Option<u64>,
+    /// The API's request ID, to quote to Gleap support.
+    pub request: Option<String>,
+    pub hint: Option<String>,
+}
+
+/// An error response from the API.
+///
+/// Error bodies come in a few shapes (a `message` string or list of
+/// strings, an `errors` list or object, an `error` or `code` name), so the
+/// fields are collected from whichever are present. Bodies that aren't JSON
+/// keep their text as the message, unless they look like an HTML page.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ApiErrorBody {
+    /// Machine-readable error name or code, e.g. `Bad Request`.
+    pub code: Option<String>,
+    pub message: Option<String>,
+    pub field_errors: Vec<FieldError>,
+    /// Request ID from the body or an `X-Request-Id` header, for support.
+    pub request_id: Option<String>,
+    /// The response body as received.
+    pub raw: String,
+}
+
+/// A validation error, tied to the request field it concerns if known.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FieldError {
+    pub field: Option<String>,
+    pub message: String,
+}
+
+impl ApiErrorBody {
+    /// Parse an error response body. `request_id` is the value of the
+    /// response's request ID header, if any.
+    pub fn parse(raw: String, request_id: Option<&str>) -> Self {
+        let mut body = Self {
+            request_id: request_id.map(str::to_string),
+            ..Self::default()
+        };
+        match serde_json::from_str::<Value>(&raw) {
+            Ok(Value::Object(map)) => {
+                body.code = ["code", "errorCode", "error"]
+                    .iter()
+                    .find_map(|key| scalar(map.get(*key)?));
+                match map.get("message") {
+                    // Validation errors: one message per failed constraint.
+                    Some(Value::Array(messages)) => {
+                        body.field_errors.extend(messages.iter().filter_map(|m| {
+                            Some(FieldError {
+                                field: None,
+                                message: scalar(m)?,
+                            })
+                        }))
+                    }
+                    Some(message) => body.message = scalar(message),
+                    None => {}
+                }
+                if let Some(errors) = map.get("errors") {
+                    body.field_errors.extend(field_errors(errors));
+                }
+                body.request_id = ["requestId", "request_id", "traceId"]
+                    .iter()
+                    .find_map(|key| scalar(map.get(*key)?))
+                    .or(body.request_id);
+            }
+            Ok(other) => body.message = scalar(&other),
+            Err(_) => {
+                let text = raw.trim();
+                if !text.is_empty() && !text.starts_with('<') {
+                    body.message = Some(text.to_string());
+                }
+            }
+        }
+        body.raw = raw;
+        body
+    }
+
+    /// Suggest valid values when a ticket enum field was rejected.
+    pub fn hint(&self) -> Option<String> {
+        let texts: Vec<String> = self
+            .message
+            .iter()
+            .chain(self.field_errors.iter().map(|e| &e.message))
+            .map(|text| text.to_lowercase())
+            .collect();
+        let fields: Vec<String> = self
+            .field_errors
+            .iter()
+            .filter_map(|e| e.field.as_deref())
+            .map(str::to_lowercase)
+            .collect();
+        let rejected = |field: &str| {
+            fields.iter().any(|f| f == field)
+                || texts.iter().any(|text| {
+                    text.split(|c: char| !c.is_alphanumeric())
+                        .any(|w| w == field)
+                        && ["invalid", "must be", "one of", "not a valid", "not allowed"]
+                            .iter()
+                            .any(|phrase| text.contains(phrase))
+                })
+        };
+
+        let (_, plural, values) = [
+            (
+                "status",
+                "statuses",
+                TicketStatus::KNOWN.map(|s| s.as_str()).to_vec(),
+            ),
+            (
+                "priority",
+                "priorities",
+                TicketPriority::KNOWN.map(|p| p.as_str()).to_vec(),
+            ),
+            (
+                "type",
+                "types",
+                TicketType::KNOWN.map(|t| t.as_str()).to_vec(),
+            ),
+        ]
+        .into_iter()
+        .find(|(field, _, _)| rejected(field))?;
+        Some(format!("valid {plural} are {}", values.join(", ")))
+    }
+}
+
+impl fmt::Display for ApiErrorBody {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match (&self.message, &self.code) {
+            (Some(message), _) => write!(f, "{message}")?,
+            (None, Some(code)) => write!(f, "{code}")?,
+            (None, None) if self.field_errors.is_empty() => write!(f, "no details given")?,
+            (None, None) => write!(f, "invalid request")?,
+        }
+        for error in &self.field_errors {
+            match &error.field {
+                Some(field) => write!(f, "\n  {field}: {}", error.message)?,
+                None => write!(f, "\n  {}", error.message)?,
+            }
+        }
+        if let Some(request_id) = &self.request_id {
+            write!(f, "\n  (request ID: {request_id})")?;
+        }
+        Ok(())
+    }
+}
+
+/// Field errors from an `errors` list (`[{field, message}]`) or object
+/// (`{field: message or [messages]}`).
+fn field_errors(errors: &Value) -> Vec<FieldError> {
+    match errors {
+        Value::Array(items) => items
+            .iter()
+            .filter_map(|item| match item {
+                Value::Object(map) => Some(FieldError {
+                    field: ["field", "property", "path", "param"]
+                        .iter()
+                        .find_map(|key| scalar(map.get(*key)?)),
+                    message: ["message", "msg", "error"]
+                        .iter()
+                        .find_map(|key| scalar(map.get(*key)?))
+                        .unwrap_or_else(|| item.to_string()),
+                }),
+                other => Some(FieldError {
+                    field: None,
+                    message: scalar(other)?,
+                }),
+            })
+            .collect(),
+        Value::Object(map) => map
+            .iter()
+            .flat_map(|(field, messages)| {
+                let messages = match messages {
+                    Value::Array(items) => items.iter().filter_map(scalar).collect(),
+                    other => scalar(other).into_iter().collect::<Vec<_>>(),
+                };
+                messages.into_iter().map(|message| FieldError {
+                    field: Some(field.clone()),
+                    message,
+                })
+            })
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
+/// A string or number as text; anything else isn't a usable message.
+fn scalar(value: &Value) -> Option<String> {
+    match value {
+        Value::String(text) if !text.trim().is_empty() => Some(text.trim().to_string()),
+        Value::Number(number) => Some(number.to_string()),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_validation_errors() {
+        let raw = r#"{
+            "statusCode": 400,
+            "error": "Bad Request",
+            "message": ["status must be one of the following values: OPEN, INPROGRESS, DONE"],
+            "requestId": "req-1"
+        }"#;
+        let body = ApiErrorBody::parse(raw.into(), Some("header-id"));
+        assert_eq!(body.code.as_deref(), Some("Bad Request"));
+        assert_eq!(body.request_id.as_deref(), Some("req-1"));
+        assert_eq!(
+            body.to_string(),
+            "Bad Request\n  \
+             status must be one of the following values: OPEN, INPROGRESS, DONE\n  \
+             (request ID: req-1)"
+        );
+        assert_eq!(
+            body.hint().as_deref(),
+            Some("valid statuses are OPEN, INPROGRESS, DONE")
+        );
+        assert_eq!(body.raw, raw);
+    }
+
+    #[test]
+    fn test_parse_errors_object_and_code() {
+        let raw = r#"{"code": 1042, "message": "Validation failed", "errors": {"priority": ["is invalid"]}}"#;
+        let body = ApiErrorBody::parse(raw.into(), None);
+        assert_eq!(body.code.as_deref(), Some("1042"));
+        assert_eq!(
+            body.field_errors,
+            vec![FieldError {
+                field: Some("priority".into()),
+                message: "is invalid".into(),
+            }]
+        );
+        assert_eq!(
+            body.to_string(),
+            "Validation failed\n  priority: is invalid"
+        );
+        assert!(body.hint().unwrap().starts_with("valid priorities are LOW"));
+    }
+
+    #[test]
+    fn test_error_report() {
+        let error = AppError::RateLimited {
+            retry_after_secs: 7,
+        };
+        let report = serde_json::to_value(error.report()).unwrap();
+        assert_eq!(report["kind"], "rate_limited");
+        assert_eq!(report["exit_code"], 6);
+        assert_eq!(report["status"], 429);
+        assert_eq!(report["retry_after"], 7);
+        assert_eq!(report["request"], Value::Null);
+
+        let error = AppError::Auth {
+            status: 403,
+            body: Box::new(ApiErrorBody::parse(
+                r#"{"message": "Forbidden"}"#.into(),
+                Some("req-9"),
+            )),
+        };
+        let report = error.report();
+        assert_eq!((report.kind, report.exit_code), ("auth", 2));
+        assert_eq!(report.status, Some(403));
+        assert_eq!(report.request.as_deref(), Some("req-9"));
+        assert!(report.hint.is_some());
+    }
+
+    #[test]
+    fn test_parse_non_json_bodies() {
+        let text = ApiErrorBody::parse("upstream timed out\n".into(), Some("abc"));
+        assert_eq!(text.to_string(), "upstream timed out\n  (request ID: abc)");
+
+        let html = ApiErrorBody::parse("<html><body>502</body></html>".into(), None);
+        assert_eq!(html.message, None);
+        assert_eq!(html.to_string(), "no details given");
+        assert_eq!(html.hint(), None);
+    }
+}
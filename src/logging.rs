@@ -0,0 +1,45 @@
+use std::io::IsTerminal;
+
+use tracing_subscriber::EnvFilter;
+
+use gleap::error::AppError;
+
+use crate::cli::LogFormat;
+
+/// Environment variable holding a log filter, in `tracing` `EnvFilter` syntax.
+const FILTER_VAR: &str = "GLEAP_LOG";
+
+/// Send log events to stderr. `GLEAP_LOG` takes precedence over `-v`, which
+/// only raises the level of the client's own events: -v for requests and
+/// retries, -vv for headers and error bodies, -vvv for full response bodies.
+pub fn init(verbose: u8, format: LogFormat) -> Result<(), AppError> {
+    let filter = match std::env::var(FILTER_VAR) {
+        Ok(directives) if !directives.trim().is_empty() => EnvFilter::try_new(&directives)
+            .map_err(|e| AppError::Config(format!("invalid {FILTER_VAR} '{directives}': {e}")))?,
+        _ => {
+            let level = match verbose {
+                0 => "warn",
+                1 => "info",
+                2 => "debug",
+                _ => "trace",
+            };
+            EnvFilter::new(format!("warn,gleap={level}"))
+        }
+    };
+
+    let builder = tracing_subscriber::fmt()
+        .with_env_filter(filter)
+        .with_writer(std::io::stderr);
+    match format {
+        LogFormat::Text => builder
+            .without_time()
+            .with_target(false)
+            .with_ansi(
+                std::io::stderr().is_terminal()
+                    && std::env::var_os("NO_COLOR").is_none_or(|v| v.is_empty()),
+            )
+            .init(),
+        LogFormat::Json => builder.json().with_current_span(true).init(),
+    }
+    Ok(())
+}
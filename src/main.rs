@@ -2,22 +2,38 @@ use clap::Parser;
 
 mod cli;
 mod commands;
+mod logging;
+mod output;
 
-use gleap::client::GleapClient;
+use gleap::client::{GleapClient, RateLimit, RetryPolicy};
 use gleap::error::AppError;
 
-use cli::{AuthAction, Cli, Domain, LogsAction, MessagesAction, TicketsAction};
+use cli::{AuthAction, Cli, Domain, ErrorFormat, LogsAction, MessagesAction, TicketsAction};
+use output::Output;
 
 #[tokio::main]
 async fn main() {
-    if let Err(e) = run().await {
-        eprintln!("Error: {}", e);
+    let cli = Cli::parse();
+    let error_format = cli.error_format();
+    if let Err(e) = run(cli).await {
+        match error_format {
+            ErrorFormat::Text => {
+                eprintln!("Error: {}", e);
+                if let Some(hint) = e.hint() {
+                    eprintln!("Hint: {hint}");
+                }
+            }
+            ErrorFormat::Json => match serde_json::to_string(&e.report()) {
+                Ok(json) => eprintln!("{json}"),
+                Err(_) => eprintln!("Error: {}", e),
+            },
+        }
         std::process::exit(e.exit_code());
     }
 }
 
-async fn run() -> Result<(), AppError> {
-    let cli = Cli::parse();
+async fn run(cli: Cli) -> Result<(), AppError> {
+    logging::init(cli.verbose, cli.log_format)?;
 
     // Auth commands don't need a client
     if let Domain::Auth { action } = cli.domain {
@@ -28,81 +44,74 @@ async fn run() -> Result<(), AppError> {
         };
     }
 
-    let client = GleapClient::resolve()?.with_verbose(cli.verbose);
+    let mut client = GleapClient::resolve()?
+        .with_retry(RetryPolicy::with_retries(cli.retries))
+        .with_dry_run(cli.dry_run);
+    if cli.rate_limit > 0 {
+        client = client.with_rate_limit(RateLimit::per_minute(cli.rate_limit));
+    }
+    let output = Output::new(&cli.output);
 
-    match cli.domain {
+    let result = match cli.domain {
         Domain::Auth { .. } => unreachable!(),
         Domain::Tickets { action } => match action {
-            TicketsAction::List {
-                status,
-                ticket_type,
-                priority,
-                sort,
-                pagination,
-            } => {
-                commands::tickets::list::run(
-                    &client,
-                    status,
-                    ticket_type,
-                    priority,
-                    sort,
-                    pagination.limit,
-                    pagination.skip,
-                )
-                .await
+            TicketsAction::List(args) => commands::tickets::list::run(&client, &output, args).await,
+            TicketsAction::Create(args) => {
+                commands::tickets::create::run(&client, &output, args).await
             }
-            TicketsAction::Create {
-                title,
-                ticket_type,
-                status,
-                priority,
-                description,
-                tags,
-            } => {
-                commands::tickets::create::run(
-                    &client,
-                    &title,
-                    ticket_type,
-                    status,
-                    priority,
-                    description,
-                    tags,
-                )
-                .await
+            TicketsAction::Get { id } => commands::tickets::get::run(&client, &output, &id).await,
+            TicketsAction::Search(args) => {
+                commands::tickets::search::run(&client, &output, args).await
+            }
+            TicketsAction::Update(args) => {
+                commands::tickets::update::run(&client, &output, args).await
+            }
+            TicketsAction::Edit { id, yes } => {
+                commands::tickets::edit::run(&client, &output, &id, yes).await
+            }
+            TicketsAction::Transcript { id, exclude_notes } => {
+                commands::tickets::transcript::run(&client, &output, &id, exclude_notes).await
             }
-            TicketsAction::Get { id } => commands::tickets::get::run(&client, &id).await,
-            TicketsAction::Search { query } => {
-                commands::tickets::search::run(&client, &query).await
+            TicketsAction::Timeline { id } => {
+                commands::tickets::timeline::run(&client, &output, &id).await
             }
-            TicketsAction::Update {
-                id,
-                status,
-                priority,
-                title,
-            } => commands::tickets::update::run(&client, &id, status, priority, title).await,
             TicketsAction::Logs { action } => match action {
-                LogsAction::Console { id } => {
-                    commands::tickets::console_logs::run(&client, &id).await
+                LogsAction::Console(args) => {
+                    commands::tickets::console_logs::run(&client, &output, args).await
                 }
-                LogsAction::Network { id } => {
-                    commands::tickets::network_logs::run(&client, &id).await
+                LogsAction::Network(args) => {
+                    commands::tickets::network_logs::run(&client, &output, args).await
                 }
                 LogsAction::Activity { id } => {
-                    commands::tickets::activity_logs::run(&client, &id).await
+                    commands::tickets::activity_logs::run(&client, &output, &id).await
                 }
             },
         },
         Domain::Messages { action } => match action {
-            MessagesAction::List { ticket, pagination } => {
-                commands::messages::list::run(&client, &ticket, pagination.limit, pagination.skip)
-                    .await
+            MessagesAction::List(args) => {
+                commands::messages::list::run(&client, &output, args).await
             }
-            MessagesAction::Note { ticket, text } => {
-                commands::messages::note::run(&client, &ticket, &text).await
+            MessagesAction::Note {
+                ticket,
+                text,
+                text_format,
+            } => {
+                commands::messages::note::run(&client, &output, &ticket, &text, &text_format).await
             }
-            MessagesAction::Reply { ticket, text } => {
-                commands::messages::reply::run(&client, &ticket, &text).await
+            MessagesAction::Reply {
+                ticket,
+                text,
+                text_format,
+            } => {
+                commands::messages::reply::run(&client, &output, &ticket, &text, &text_format).await
             }
         },
+        Domain::Api(args) => commands::api::run(&client, &output, args).await,
+    };
+
+    match result {
+        // A dry run ends at the first request that would change something.
+        Err(AppError::DryRun(preview)) => output.request_preview(&preview),
+        result => result,
     }
 }
@@ -0,0 +1,233 @@
+//! Dotted-path edits of a ticket's free-form `customData` and `formData`
+//! objects, e.g. `customData.linkedPr=1234`.
+//!
+//! The update endpoint replaces these objects wholesale, so edits are
+//! applied to the current object (read-modify-write) and the result is sent
+//! back in full.
+
+use std::fmt;
+use std::str::FromStr;
+
+use serde_json::{Map, Value};
+
+use crate::error::AppError;
+
+/// Which free-form ticket object a path points into.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DataField {
+    CustomData,
+    FormData,
+}
+
+impl DataField {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            DataField::CustomData => "customData",
+            DataField::FormData => "formData",
+        }
+    }
+}
+
+/// A path such as `customData.account.id`: the object it starts in and the
+/// keys below it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FieldPath {
+    pub field: DataField,
+    pub keys: Vec<String>,
+}
+
+impl FromStr for FieldPath {
+    type Err = String;
+
+    fn from_str(path: &str) -> Result<Self, Self::Err> {
+        let mut segments = path.split('.');
+        let field = match segments.next() {
+            Some("customData") => DataField::CustomData,
+            Some("formData") => DataField::FormData,
+            _ => {
+                return Err(format!(
+                    "invalid path '{path}': must start with customData. or formData."
+                ));
+            }
+        };
+        let keys: Vec<String> = segments.map(str::to_string).collect();
+        if keys.is_empty() || keys.iter().any(String::is_empty) {
+            return Err(format!(
+                "invalid path '{path}': expected e.g. {}.key",
+                field.as_str()
+            ));
+        }
+        Ok(Self { field, keys })
+    }
+}
+
+impl fmt::Display for FieldPath {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}.{}", self.field.as_str(), self.keys.join("."))
+    }
+}
+
+/// Set or remove the value at a path.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FieldEdit {
+    pub path: FieldPath,
+    /// The new value, or `None` to remove the key.
+    pub value: Option<Value>,
+}
+
+impl FieldEdit {
+    /// Parse `path=value`, inferring the value's type (see [`infer_value`]).
+    pub fn parse_set(arg: &str) -> Result<Self, String> {
+        let (path, value) = arg
+            .split_once('=')
+            .ok_or_else(|| format!("invalid assignment '{arg}': expected path=value"))?;
+        Ok(Self {
+            path: path.parse()?,
+            value: Some(infer_value(value)),
+        })
+    }
+
+    /// Parse a path to remove.
+    pub fn parse_unset(arg: &str) -> Result<Self, String> {
+        Ok(Self {
+            path: arg.parse()?,
+            value: None,
+        })
+    }
+
+    /// Apply the edit to the object named by `self.path.field`. Missing
+    /// intermediate objects are created; a scalar in the way is an error.
+    pub fn apply(&self, data: &mut Map<String, Value>) -> Result<(), AppError> {
+        let (last, parents) = self
+            .path
+            .keys
+            .split_last()
+            .expect("paths have at least one key");
+
+        let mut current = data;
+        for key in parents {
+            let next = match &self.value {
+                Some(_) => current
+                    .entry(key.clone())
+                    .or_insert_with(|| Value::Object(Map::new())),
+                // Nothing to remove below a missing key.
+                None => match current.get_mut(key) {
+                    Some(next) => next,
+                    None => return Ok(()),
+                },
+            };
+            if self.value.is_none() && !next.is_object() {
+                return Ok(());
+            }
+            if next.is_null() {
+                *next = Value::Object(Map::new());
+            }
+            current = match next {
+                Value::Object(map) => map,
+                _ => {
+                    return Err(AppError::Config(format!(
+                        "cannot edit {}: '{key}' is not an object",
+                        self.path
+                    )));
+                }
+            };
+        }
+
+        match &self.value {
+            Some(value) => {
+                current.insert(last.clone(), value.clone());
+            }
+            None => {
+                current.remove(last);
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Interpret a command-line value as JSON where it parses as a number,
+/// boolean, `null`, object, array or quoted string, and as a plain string
+/// otherwise. Quote a value (`'"1234"'`) to keep it a string.
+pub fn infer_value(raw: &str) -> Value {
+    serde_json::from_str(raw).unwrap_or_else(|_| Value::String(raw.to_string()))
+}
+
+/// Apply the edits for `field` to a copy of `current` (a ticket's
+/// `customData` or `formData`). Returns the new object if it differs.
+pub fn apply_edits(
+    current: Option<&Value>,
+    field: DataField,
+    edits: &[FieldEdit],
+) -> Result<Option<Map<String, Value>>, AppError> {
+    let original = match current {
+        Some(Value::Object(map)) => map.clone(),
+        _ => Map::new(),
+    };
+    let mut data = original.clone();
+    for edit in edits.iter().filter(|edit| edit.path.field == field) {
+        edit.apply(&mut data)?;
+    }
+    Ok((data != original).then_some(data))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_infer_value() {
+        assert_eq!(infer_value("1234"), json!(1234));
+        assert_eq!(infer_value("1.5"), json!(1.5));
+        assert_eq!(infer_value("true"), json!(true));
+        assert_eq!(infer_value("null"), json!(null));
+        assert_eq!(infer_value(r#"{"a":[1]}"#), json!({ "a": [1] }));
+        assert_eq!(infer_value(r#""1234""#), json!("1234"));
+        assert_eq!(infer_value("pro plan"), json!("pro plan"));
+    }
+
+    #[test]
+    fn test_parse_paths() {
+        let edit = FieldEdit::parse_set("customData.account.id=acc=42").unwrap();
+        assert_eq!(edit.path.field, DataField::CustomData);
+        assert_eq!(edit.path.keys, vec!["account", "id"]);
+        assert_eq!(edit.value, Some(json!("acc=42")));
+
+        assert!(FieldEdit::parse_set("customData.plan").is_err());
+        assert!(FieldEdit::parse_unset("title").is_err());
+        assert!(FieldEdit::parse_unset("formData").is_err());
+        assert!(FieldEdit::parse_unset("formData..x").is_err());
+    }
+
+    #[test]
+    fn test_apply_edits() {
+        let current = json!({ "plan": "free", "account": { "id": 1, "region": "eu" } });
+        let edits = [
+            FieldEdit::parse_set("customData.plan=pro").unwrap(),
+            FieldEdit::parse_set("customData.links.pr=1234").unwrap(),
+            FieldEdit::parse_unset("customData.account.region").unwrap(),
+            FieldEdit::parse_unset("customData.missing.key").unwrap(),
+            FieldEdit::parse_set("formData.ignored=1").unwrap(),
+        ];
+        let updated = apply_edits(Some(&current), DataField::CustomData, &edits)
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            Value::Object(updated),
+            json!({ "plan": "pro", "account": { "id": 1 }, "links": { "pr": 1234 } })
+        );
+    }
+
+    #[test]
+    fn test_unchanged_and_conflicting_edits() {
+        let current = json!({ "plan": "pro" });
+        let same = [FieldEdit::parse_set("customData.plan=pro").unwrap()];
+        assert_eq!(
+            apply_edits(Some(&current), DataField::CustomData, &same).unwrap(),
+            None
+        );
+
+        let nested = [FieldEdit::parse_set("customData.plan.tier=1").unwrap()];
+        assert!(apply_edits(Some(&current), DataField::CustomData, &nested).is_err());
+    }
+}
@@ -0,0 +1,775 @@
+//! Logs captured with a ticket: browser console output, network requests,
+//! and the ticket's activity history.
+//!
+//! The API documents these endpoints only as JSON objects, so the models
+//! follow the field names the Gleap SDKs record (`log`/`date`/`priority` for
+//! console entries, `type`/`request`/`response` for network entries), accept
+//! common alternatives, and keep everything else in `extra`. Fields of an
+//! unexpected type are read as missing rather than failing the whole list,
+//! since one odd entry shouldn't hide the others.
+
+use std::fmt;
+use std::str::FromStr;
+
+use chrono::{DateTime, Utc};
+use regex::Regex;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use serde_json::{Map, Value};
+
+use super::ticket::UserRef;
+
+/// Severity of a console entry. Unrecognised levels are kept verbatim.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LogLevel {
+    Debug,
+    Info,
+    Warning,
+    Error,
+    Other(String),
+}
+
+impl LogLevel {
+    pub fn as_str(&self) -> &str {
+        match self {
+            LogLevel::Debug => "DEBUG",
+            LogLevel::Info => "INFO",
+            LogLevel::Warning => "WARNING",
+            LogLevel::Error => "ERROR",
+            LogLevel::Other(level) => level,
+        }
+    }
+
+    /// Parse a level name case-insensitively, accepting common aliases
+    /// (`warn`, `log`, `trace`, ...).
+    pub fn parse(level: &str) -> Self {
+        match level.to_ascii_lowercase().as_str() {
+            "debug" | "trace" | "verbose" => LogLevel::Debug,
+            "info" | "log" => LogLevel::Info,
+            "warn" | "warning" => LogLevel::Warning,
+            "error" | "err" => LogLevel::Error,
+            _ => LogLevel::Other(level.to_string()),
+        }
+    }
+}
+
+impl fmt::Display for LogLevel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl Serialize for LogLevel {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+impl<'de> Deserialize<'de> for LogLevel {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Ok(match Value::deserialize(deserializer)? {
+            Value::String(level) => LogLevel::parse(&level),
+            other => LogLevel::Other(other.to_string()),
+        })
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConsoleLog {
+    #[serde(rename = "priority", alias = "level", default)]
+    pub level: Option<LogLevel>,
+
+    #[serde(
+        rename = "log",
+        alias = "message",
+        alias = "text",
+        default,
+        deserialize_with = "text"
+    )]
+    pub message: Option<String>,
+
+    #[serde(
+        rename = "date",
+        alias = "timestamp",
+        alias = "createdAt",
+        default,
+        deserialize_with = "timestamp"
+    )]
+    pub timestamp: Option<String>,
+
+    #[serde(
+        alias = "stackTrace",
+        alias = "trace",
+        default,
+        deserialize_with = "text"
+    )]
+    pub stack: Option<String>,
+
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+impl ConsoleLog {
+    /// When the entry was logged, if the timestamp is RFC 3339.
+    pub fn time(&self) -> Option<DateTime<Utc>> {
+        parse_time(self.timestamp.as_deref()?)
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NetworkLog {
+    /// HTTP method (`GET`, `POST`, ...).
+    #[serde(
+        rename = "type",
+        alias = "method",
+        default,
+        deserialize_with = "lenient"
+    )]
+    pub method: Option<String>,
+
+    #[serde(default, deserialize_with = "lenient")]
+    pub url: Option<String>,
+
+    #[serde(
+        rename = "date",
+        alias = "timestamp",
+        alias = "createdAt",
+        default,
+        deserialize_with = "timestamp"
+    )]
+    pub timestamp: Option<String>,
+
+    /// Time to complete the request, in milliseconds.
+    #[serde(default, deserialize_with = "lenient")]
+    pub duration: Option<f64>,
+
+    #[serde(default, deserialize_with = "lenient")]
+    pub success: Option<bool>,
+
+    #[serde(default, deserialize_with = "or_missing")]
+    pub request: Option<NetworkRequest>,
+
+    #[serde(default, deserialize_with = "or_missing")]
+    pub response: Option<NetworkResponse>,
+
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+impl NetworkLog {
+    /// Response status code, if a response was captured.
+    pub fn status(&self) -> Option<u16> {
+        self.response.as_ref().and_then(|r| r.status)
+    }
+
+    /// When the request started, if the timestamp is RFC 3339.
+    pub fn time(&self) -> Option<DateTime<Utc>> {
+        parse_time(self.timestamp.as_deref()?)
+    }
+
+    /// Size of the response body in bytes: the captured body's length, or
+    /// the `Content-Length` header when the body wasn't captured.
+    pub fn response_size(&self) -> Option<u64> {
+        let response = self.response.as_ref()?;
+        if let Some(body) = &response.body {
+            return Some(body_text(body).len() as u64);
+        }
+        header(response.headers.as_ref(), "content-length")?
+            .trim()
+            .parse()
+            .ok()
+    }
+}
+
+/// A body as sent over the wire: strings as-is, anything else as JSON.
+pub fn body_text(body: &Value) -> String {
+    match body {
+        Value::String(text) => text.clone(),
+        other => other.to_string(),
+    }
+}
+
+/// Look up a header case-insensitively in a captured header map.
+pub fn header<'a>(headers: Option<&'a Map<String, Value>>, name: &str) -> Option<&'a str> {
+    headers?
+        .iter()
+        .find(|(key, _)| key.eq_ignore_ascii_case(name))
+        .and_then(|(_, value)| value.as_str())
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct NetworkRequest {
+    #[serde(default, deserialize_with = "or_missing")]
+    pub headers: Option<Map<String, Value>>,
+
+    /// Request body: a string, or JSON if the SDK captured it parsed.
+    #[serde(rename = "payload", alias = "body", default)]
+    pub body: Option<Value>,
+
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct NetworkResponse {
+    #[serde(default, deserialize_with = "lenient")]
+    pub status: Option<u16>,
+
+    #[serde(rename = "statusText", default, deserialize_with = "lenient")]
+    pub status_text: Option<String>,
+
+    #[serde(default, deserialize_with = "or_missing")]
+    pub headers: Option<Map<String, Value>>,
+
+    /// Response body: a string, or JSON if the SDK captured it parsed.
+    #[serde(rename = "responseText", alias = "body", default)]
+    pub body: Option<Value>,
+
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ActivityLog {
+    /// What happened, e.g. `STATUS_CHANGED` or `ASSIGNED`.
+    #[serde(
+        rename = "type",
+        alias = "action",
+        alias = "event",
+        default,
+        deserialize_with = "lenient"
+    )]
+    pub action: Option<String>,
+
+    /// The ticket field that changed, if any (`status`, `priority`, ...).
+    #[serde(default, deserialize_with = "lenient")]
+    pub field: Option<String>,
+
+    #[serde(rename = "oldValue", alias = "from", default)]
+    pub old_value: Option<Value>,
+
+    #[serde(rename = "newValue", alias = "to", default)]
+    pub new_value: Option<Value>,
+
+    /// Team member who made the change; absent for customer and system actions.
+    #[serde(alias = "actor", default, deserialize_with = "user")]
+    pub user: Option<UserRef>,
+
+    #[serde(
+        rename = "createdAt",
+        alias = "date",
+        alias = "timestamp",
+        default,
+        deserialize_with = "timestamp"
+    )]
+    pub created_at: Option<String>,
+
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+impl ActivityLog {
+    /// When the change happened, if the timestamp is RFC 3339.
+    pub fn time(&self) -> Option<DateTime<Utc>> {
+        parse_time(self.created_at.as_deref()?)
+    }
+
+    /// Whether this entry records a change of the ticket's status.
+    pub fn is_status_change(&self) -> bool {
+        self.field.as_deref() == Some("status") || self.action.as_deref() == Some("STATUS_CHANGED")
+    }
+}
+
+/// Which console entries to keep. Empty or unset criteria match everything.
+#[derive(Debug, Clone, Default)]
+pub struct ConsoleFilter {
+    /// Keep entries at any of these levels.
+    pub levels: Vec<LogLevel>,
+    /// Keep entries whose message or stack trace matches.
+    pub pattern: Option<Regex>,
+    /// Keep entries logged at or after this time.
+    pub since: Option<DateTime<Utc>>,
+    /// Keep entries logged at or before this time.
+    pub until: Option<DateTime<Utc>>,
+    /// Keep only the last `n` matching entries.
+    pub tail: Option<usize>,
+}
+
+impl ConsoleFilter {
+    /// Apply the filter, keeping the original order. Entries without a
+    /// readable timestamp are dropped when filtering by time.
+    pub fn apply(&self, logs: Vec<ConsoleLog>) -> Vec<ConsoleLog> {
+        let mut logs: Vec<ConsoleLog> = logs.into_iter().filter(|log| self.matches(log)).collect();
+        if let Some(tail) = self.tail {
+            logs.drain(..logs.len().saturating_sub(tail));
+        }
+        logs
+    }
+
+    fn matches(&self, log: &ConsoleLog) -> bool {
+        if !self.levels.is_empty()
+            && !log
+                .level
+                .as_ref()
+                .is_some_and(|level| self.levels.contains(level))
+        {
+            return false;
+        }
+        if let Some(pattern) = &self.pattern {
+            let text = [log.message.as_deref(), log.stack.as_deref()];
+            if !text.into_iter().flatten().any(|t| pattern.is_match(t)) {
+                return false;
+            }
+        }
+        if self.since.is_some() || self.until.is_some() {
+            let Some(at) = log.time() else {
+                return false;
+            };
+            if self.since.is_some_and(|since| at < since)
+                || self.until.is_some_and(|until| at > until)
+            {
+                return false;
+            }
+        }
+        true
+    }
+}
+
+/// A `--status` criterion: an exact code (`404`) or a class (`5xx`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StatusMatch {
+    Code(u16),
+    /// The hundreds digit, e.g. `4` for `4xx`.
+    Class(u16),
+}
+
+impl StatusMatch {
+    pub fn matches(&self, status: u16) -> bool {
+        match self {
+            StatusMatch::Code(code) => status == *code,
+            StatusMatch::Class(class) => status / 100 == *class,
+        }
+    }
+}
+
+impl FromStr for StatusMatch {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let lower = value.to_ascii_lowercase();
+        let parsed = match lower.strip_suffix("xx") {
+            Some(class) => class.parse().ok().map(StatusMatch::Class),
+            None => lower.parse().ok().map(StatusMatch::Code),
+        };
+        parsed
+            .filter(|m| match m {
+                StatusMatch::Code(code) => (100..600).contains(code),
+                StatusMatch::Class(class) => (1..6).contains(class),
+            })
+            .ok_or_else(|| {
+                format!("invalid status '{value}': expected a code like 404 or a class like 5xx")
+            })
+    }
+}
+
+/// Which network entries to keep. Empty or unset criteria match everything.
+#[derive(Debug, Clone, Default)]
+pub struct NetworkFilter {
+    /// Keep responses matching any of these; requests without a response
+    /// never match.
+    pub statuses: Vec<StatusMatch>,
+    /// Keep requests using any of these methods (case-insensitive).
+    pub methods: Vec<String>,
+    /// Keep requests whose full URL matches this glob (`*` and `?`).
+    pub url_glob: Option<String>,
+    /// Keep requests that took longer than this many milliseconds.
+    pub slower_than: Option<f64>,
+}
+
+impl NetworkFilter {
+    pub fn apply(&self, logs: Vec<NetworkLog>) -> Vec<NetworkLog> {
+        logs.into_iter().filter(|log| self.matches(log)).collect()
+    }
+
+    fn matches(&self, log: &NetworkLog) -> bool {
+        if !self.statuses.is_empty()
+            && !log
+                .status()
+                .is_some_and(|status| self.statuses.iter().any(|m| m.matches(status)))
+        {
+            return false;
+        }
+        if !self.methods.is_empty()
+            && !log
+                .method
+                .as_deref()
+                .is_some_and(|method| self.methods.iter().any(|m| m.eq_ignore_ascii_case(method)))
+        {
+            return false;
+        }
+        if let Some(glob) = &self.url_glob
+            && !log.url.as_deref().is_some_and(|url| glob_match(glob, url))
+        {
+            return false;
+        }
+        if let Some(limit) = self.slower_than
+            && !log.duration.is_some_and(|duration| duration > limit)
+        {
+            return false;
+        }
+        true
+    }
+}
+
+/// Match `text` against a glob where `*` is any run of characters and `?`
+/// is any single character.
+fn glob_match(pattern: &str, text: &str) -> bool {
+    let pattern: Vec<char> = pattern.chars().collect();
+    let text: Vec<char> = text.chars().collect();
+    let (mut p, mut t) = (0, 0);
+    // Position after the last `*`, and the text position it was tried at.
+    let mut star: Option<(usize, usize)> = None;
+
+    while t < text.len() {
+        if p < pattern.len() && (pattern[p] == '?' || pattern[p] == text[t]) {
+            p += 1;
+            t += 1;
+        } else if p < pattern.len() && pattern[p] == '*' {
+            star = Some((p + 1, t));
+            p += 1;
+        } else if let Some((after_star, tried)) = star {
+            p = after_star;
+            t = tried + 1;
+            star = Some((after_star, t));
+        } else {
+            return false;
+        }
+    }
+    pattern[p..].iter().all(|&c| c == '*')
+}
+
+/// Extract log entries from a log endpoint's response: either a bare array,
+/// or an object holding the array under some key (the first array found,
+/// trying `preferred` keys first).
+pub fn entries<T: DeserializeOwned>(
+    response: Value,
+    preferred: &[&str],
+) -> Result<Vec<T>, serde_json::Error> {
+    let list = match response {
+        Value::Array(items) => Value::Array(items),
+        Value::Object(mut map) => preferred
+            .iter()
+            .find(|key| map.get(**key).is_some_and(Value::is_array))
+            .map(|key| key.to_string())
+            .or_else(|| {
+                map.iter()
+                    .find(|(_, value)| value.is_array())
+                    .map(|(key, _)| key.clone())
+            })
+            .and_then(|key| map.remove(&key))
+            .unwrap_or(Value::Array(Vec::new())),
+        _ => Value::Array(Vec::new()),
+    };
+    serde_json::from_value(list)
+}
+
+fn parse_time(ts: &str) -> Option<DateTime<Utc>> {
+    DateTime::parse_from_rfc3339(ts).ok().map(|dt| dt.to_utc())
+}
+
+/// Accept a value of the expected type or a string that parses as one;
+/// anything else is read as missing.
+fn lenient<'de, D, T>(deserializer: D) -> Result<Option<T>, D::Error>
+where
+    D: Deserializer<'de>,
+    T: DeserializeOwned + FromStr,
+{
+    Ok(match Option::<Value>::deserialize(deserializer)? {
+        Some(Value::String(s)) => s.trim().parse().ok(),
+        Some(value) => serde_json::from_value(value).ok(),
+        None => None,
+    })
+}
+
+/// Accept a value of the expected type; anything else is read as missing.
+fn or_missing<'de, D, T>(deserializer: D) -> Result<Option<T>, D::Error>
+where
+    D: Deserializer<'de>,
+    T: DeserializeOwned,
+{
+    Ok(Option::<Value>::deserialize(deserializer)?
+        .and_then(|value| serde_json::from_value(value).ok()))
+}
+
+/// Accept a user object, or a bare user ID; anything else is read as missing.
+fn user<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<UserRef>, D::Error> {
+    Ok(match Option::<Value>::deserialize(deserializer)? {
+        Some(Value::String(id)) => Some(UserRef {
+            id: Some(id),
+            email: None,
+            first_name: None,
+            last_name: None,
+        }),
+        Some(value) => serde_json::from_value(value).ok(),
+        None => None,
+    })
+}
+
+/// Accept an RFC 3339 string, or a Unix epoch number in seconds or
+/// milliseconds (converted to RFC 3339); anything else is read as missing.
+fn timestamp<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<String>, D::Error> {
+    Ok(match Option::<Value>::deserialize(deserializer)? {
+        Some(Value::String(s)) => Some(s),
+        Some(Value::Number(n)) => n.as_f64().and_then(|epoch| {
+            // Anything past 1e11 seconds (year 5138) must be milliseconds.
+            let millis = if epoch.abs() < 1e11 {
+                epoch * 1000.0
+            } else {
+                epoch
+            };
+            DateTime::from_timestamp_millis(millis as i64)
+                .map(|at| at.to_rfc3339_opts(chrono::SecondsFormat::Millis, true))
+        }),
+        _ => None,
+    })
+}
+
+/// Accept a string, or render any other JSON value (e.g. a logged object)
+/// as compact JSON.
+fn text<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<String>, D::Error> {
+    Ok(match Option::<Value>::deserialize(deserializer)? {
+        None | Some(Value::Null) => None,
+        Some(Value::String(s)) => Some(s),
+        Some(other) => Some(other.to_string()),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_mixed_type_entries() {
+        let console: Vec<ConsoleLog> = entries(
+            json!([
+                { "log": "epoch", "date": 1772359200000u64, "priority": 3 },
+                { "log": "odd", "date": { "at": "noon" } }
+            ]),
+            &[],
+        )
+        .unwrap();
+        assert_eq!(
+            console[0].timestamp.as_deref(),
+            Some("2026-03-01T10:00:00.000Z")
+        );
+        assert_eq!(console[0].level, Some(LogLevel::Other("3".into())));
+        assert_eq!(console[1].timestamp, None);
+
+        let network: Vec<NetworkLog> = entries(
+            json!([
+                { "url": "/a", "duration": "120.5", "success": "yes", "response": { "status": "200" } },
+                { "url": "/b", "duration": null, "response": { "status": "n/a" } },
+                { "url": "/c", "date": 1772359200, "duration": 80, "response": { "status": 404 } }
+            ]),
+            &[],
+        )
+        .unwrap();
+        assert_eq!(network[0].duration, Some(120.5));
+        assert_eq!(network[0].success, None);
+        assert_eq!(network[0].status(), Some(200));
+        assert_eq!((network[1].duration, network[1].status()), (None, None));
+        assert_eq!(network[2].time(), parse_time("2026-03-01T10:00:00Z"));
+        assert_eq!(network[2].status(), Some(404));
+    }
+
+    #[test]
+    fn test_mistyped_network_fields() {
+        let network: Vec<NetworkLog> = entries(
+            json!([
+                { "type": 7, "url": 42, "request": { "headers": "Accept: */*" } },
+                { "url": "/b", "request": "GET /b", "response": { "statusText": 0, "headers": ["x"] } }
+            ]),
+            &[],
+        )
+        .unwrap();
+        assert_eq!(
+            (network[0].method.as_deref(), network[0].url.as_deref()),
+            (None, None)
+        );
+        assert_eq!(
+            network[0].request.as_ref().map(|r| r.headers.is_none()),
+            Some(true)
+        );
+        assert!(network[1].request.is_none());
+        let response = network[1].response.as_ref().unwrap();
+        assert_eq!(
+            (response.status_text.as_deref(), response.headers.is_none()),
+            (None, true)
+        );
+    }
+
+    #[test]
+    fn test_mistyped_activity_fields() {
+        let logs: Vec<ActivityLog> = entries(
+            json!([
+                { "type": 3, "field": ["status"], "user": "u1", "createdAt": 1772359200000u64 },
+                { "type": "ASSIGNED", "user": 5, "createdAt": { "at": "noon" } }
+            ]),
+            &[],
+        )
+        .unwrap();
+        assert_eq!(
+            (logs[0].action.as_deref(), logs[0].field.as_deref()),
+            (None, None)
+        );
+        assert_eq!(
+            logs[0].user.as_ref().and_then(|u| u.id.as_deref()),
+            Some("u1")
+        );
+        assert_eq!(logs[0].time(), parse_time("2026-03-01T10:00:00Z"));
+        assert!(logs[1].user.is_none());
+        assert_eq!(logs[1].created_at, None);
+    }
+
+    #[test]
+    fn test_console_entries() {
+        let response = json!({
+            "consoleLog": [
+                { "log": "Loaded", "date": "2026-03-01T10:00:00.000Z", "priority": "INFO" },
+                { "message": { "code": 42 }, "level": "warn", "url": "/app" },
+                { "log": "Boom", "priority": "ERROR", "stack": "at main.js:1" }
+            ]
+        });
+        let logs: Vec<ConsoleLog> = entries(response, &["logs", "consoleLog"]).unwrap();
+
+        assert_eq!(logs.len(), 3);
+        assert_eq!(logs[0].level, Some(LogLevel::Info));
+        assert_eq!(logs[1].level, Some(LogLevel::Warning));
+        assert_eq!(logs[1].message.as_deref(), Some(r#"{"code":42}"#));
+        assert_eq!(logs[1].extra["url"], json!("/app"));
+        assert_eq!(logs[2].stack.as_deref(), Some("at main.js:1"));
+    }
+
+    #[test]
+    fn test_console_filter() {
+        let logs: Vec<ConsoleLog> = serde_json::from_value(json!([
+            { "log": "a", "priority": "INFO", "date": "2026-03-01T10:00:00Z" },
+            { "log": "b failed", "priority": "ERROR", "date": "2026-03-01T10:00:05Z" },
+            { "log": "c", "priority": "WARNING", "date": "2026-03-01T10:00:10Z", "stack": "at failed()" },
+            { "log": "d failed", "priority": "ERROR" }
+        ]))
+        .unwrap();
+        let messages = |filter: ConsoleFilter| -> Vec<String> {
+            filter
+                .apply(logs.clone())
+                .into_iter()
+                .filter_map(|log| log.message)
+                .collect()
+        };
+
+        let errors = ConsoleFilter {
+            levels: vec![LogLevel::Warning, LogLevel::Error],
+            pattern: Some(Regex::new("fail").unwrap()),
+            ..Default::default()
+        };
+        assert_eq!(messages(errors), vec!["b failed", "c", "d failed"]);
+
+        let window = ConsoleFilter {
+            since: parse_time("2026-03-01T10:00:05Z"),
+            tail: Some(1),
+            ..Default::default()
+        };
+        assert_eq!(messages(window), vec!["c"]);
+    }
+
+    #[test]
+    fn test_network_filter() {
+        let logs: Vec<NetworkLog> = serde_json::from_value(json!([
+            { "type": "GET", "url": "https://api.example.com/v1/me", "duration": 90, "response": { "status": 401 } },
+            { "type": "POST", "url": "https://api.example.com/v1/login", "duration": 1800, "response": { "status": 500 } },
+            { "type": "GET", "url": "https://cdn.example.com/logo.png", "duration": 30, "response": { "status": 200 } },
+            { "type": "GET", "url": "https://api.example.com/v1/slow", "duration": 5000 }
+        ]))
+        .unwrap();
+        let urls = |filter: NetworkFilter| -> Vec<String> {
+            filter
+                .apply(logs.clone())
+                .into_iter()
+                .filter_map(|log| log.url)
+                .collect()
+        };
+
+        let failing = NetworkFilter {
+            statuses: vec!["4xx".parse().unwrap(), "500".parse().unwrap()],
+            url_glob: Some("*api.example.com/v1/*".into()),
+            ..Default::default()
+        };
+        assert_eq!(
+            urls(failing),
+            vec![
+                "https://api.example.com/v1/me",
+                "https://api.example.com/v1/login"
+            ]
+        );
+
+        let slow_gets = NetworkFilter {
+            methods: vec!["get".into()],
+            slower_than: Some(500.0),
+            ..Default::default()
+        };
+        assert_eq!(urls(slow_gets), vec!["https://api.example.com/v1/slow"]);
+
+        assert!("6xx".parse::<StatusMatch>().is_err());
+        assert!(glob_match("*.png", "https://cdn.example.com/logo.png"));
+        assert!(glob_match(
+            "https://?pi.*/v1/*",
+            "https://api.example.com/v1/me"
+        ));
+        assert!(!glob_match("*/v2/*", "https://api.example.com/v1/me"));
+    }
+
+    #[test]
+    fn test_network_entry_round_trip() {
+        let entry = json!({
+            "type": "POST",
+            "url": "https://api.example.com/login",
+            "date": "2026-03-01T10:00:00.000Z",
+            "duration": 812.5,
+            "success": false,
+            "request": { "headers": { "Content-Type": "application/json" }, "payload": "{\"user\":\"a\"}" },
+            "response": {
+                "status": 500,
+                "statusText": "Internal Server Error",
+                "headers": { "Retry-After": "5" },
+                "responseText": "oops"
+            }
+        });
+        let logs: Vec<NetworkLog> = entries(json!([entry.clone()]), &[]).unwrap();
+
+        assert_eq!(logs[0].method.as_deref(), Some("POST"));
+        assert_eq!(logs[0].status(), Some(500));
+        assert_eq!(logs[0].duration, Some(812.5));
+        assert_eq!(serde_json::to_value(&logs[0]).unwrap(), entry);
+    }
+
+    #[test]
+    fn test_activity_entries() {
+        let response = json!({ "activities": [{
+            "type": "STATUS_CHANGED",
+            "field": "status",
+            "from": "OPEN",
+            "to": "DONE",
+            "actor": { "email": "ann@example.com" },
+            "date": "2026-03-01T10:00:00.000Z"
+        }]});
+        let logs: Vec<ActivityLog> = entries(response, &[]).unwrap();
+
+        assert_eq!(logs[0].action.as_deref(), Some("STATUS_CHANGED"));
+        assert_eq!(logs[0].new_value, Some(json!("DONE")));
+        assert_eq!(
+            logs[0].user.as_ref().and_then(|u| u.email.as_deref()),
+            Some("ann@example.com")
+        );
+    }
+}
@@ -1,8 +1,10 @@
+use std::fmt;
+
 use serde::{Deserialize, Serialize};
 
 use super::ticket::{SessionRef, UserRef};
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "SCREAMING_SNAKE_CASE")]
 pub enum MessageType {
     Text,
@@ -16,6 +18,37 @@ pub enum MessageType {
     Unknown,
 }
 
+impl MessageType {
+    pub const KNOWN: [MessageType; 7] = [
+        MessageType::Text,
+        MessageType::Note,
+        MessageType::Bot,
+        MessageType::BotReply,
+        MessageType::UserText,
+        MessageType::SharedComment,
+        MessageType::FeedbackUpdated,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            MessageType::Text => "TEXT",
+            MessageType::Note => "NOTE",
+            MessageType::Bot => "BOT",
+            MessageType::BotReply => "BOT_REPLY",
+            MessageType::UserText => "USER_TEXT",
+            MessageType::SharedComment => "SHARED_COMMENT",
+            MessageType::FeedbackUpdated => "FEEDBACK_UPDATED",
+            MessageType::Unknown => "UNKNOWN",
+        }
+    }
+}
+
+impl fmt::Display for MessageType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
 /// The `data` envelope returned by the API for message content.
 /// The actual message text lives in `content`, which can be a plain
 /// string or a rich document object.
@@ -77,6 +110,26 @@ pub struct Message {
     pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
+impl Message {
+    /// Whether this is a reply from a team member that the customer sees:
+    /// not a note, status event, bot message or customer message. Only
+    /// messages with a team `user` count, so system or imported messages
+    /// without an author don't.
+    pub fn is_team_reply(&self) -> bool {
+        match self.message_type {
+            Some(
+                MessageType::Note
+                | MessageType::FeedbackUpdated
+                | MessageType::Bot
+                | MessageType::BotReply
+                | MessageType::UserText,
+            ) => false,
+            _ if self.bot == Some(true) => false,
+            _ => self.user.is_some(),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Attachment {
     #[serde(default)]
@@ -108,8 +161,38 @@ pub struct CreateMessageRequest {
 #[derive(Debug, Clone, Default)]
 pub struct MessageFilters {
     pub ticket: Option<String>,
+    /// One type or a comma-separated list (e.g. `NOTE,TEXT`).
     pub message_type: Option<String>,
     pub bot: Option<bool>,
+    /// Only messages created at or after this date or timestamp.
+    pub since: Option<String>,
+    /// Only messages created at or before this date or timestamp.
+    pub until: Option<String>,
+    /// Language code to auto-translate message content into (e.g. `de`).
+    pub language: Option<String>,
     pub limit: Option<u64>,
     pub skip: Option<u64>,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_is_team_reply() {
+        let message = |value| serde_json::from_value::<Message>(value).unwrap();
+        assert!(
+            message(json!({ "id": "m1", "type": "TEXT", "user": { "id": "u1" } })).is_team_reply()
+        );
+        assert!(
+            !message(json!({ "id": "m2", "type": "TEXT", "session": { "id": "s1" } }))
+                .is_team_reply()
+        );
+        assert!(!message(json!({ "id": "m3", "type": "TEXT" })).is_team_reply());
+        assert!(!message(json!({ "id": "m4" })).is_team_reply());
+        assert!(
+            !message(json!({ "id": "m5", "type": "NOTE", "user": { "id": "u1" } })).is_team_reply()
+        );
+    }
+}
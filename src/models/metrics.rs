@@ -0,0 +1,256 @@
+//! Lifecycle metrics for a ticket, derived from its activity log and
+//! messages: how long the first team reply took, how long the ticket spent
+//! in each status, when it was resolved and how often it was reopened.
+//!
+//! Durations are whole seconds so they can be summed and averaged across
+//! tickets. Timestamps that aren't RFC 3339 are ignored.
+
+use chrono::{DateTime, SecondsFormat, Utc};
+use serde::Serialize;
+
+use super::logs::ActivityLog;
+use super::message::Message;
+use super::ticket::{Ticket, TicketStatus};
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TicketMetrics {
+    /// When the ticket was reported.
+    pub created_at: Option<String>,
+
+    /// When a team member first replied to the customer.
+    pub first_response_at: Option<String>,
+    pub time_to_first_response_secs: Option<i64>,
+
+    /// When the ticket last moved to `DONE`, if that's where it is now.
+    pub resolved_at: Option<String>,
+    pub time_to_resolution_secs: Option<i64>,
+
+    /// Total time spent in each status, in the order the statuses were
+    /// first entered. Time in an unresolved current status runs until now.
+    pub time_in_status: Vec<StatusTime>,
+
+    /// How many times the ticket left `DONE` again.
+    pub reopen_count: u32,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct StatusTime {
+    pub status: String,
+    pub secs: i64,
+}
+
+/// A status transition taken from the activity log.
+struct StatusChange<'a> {
+    at: DateTime<Utc>,
+    from: Option<&'a str>,
+    to: &'a str,
+}
+
+/// Compute a ticket's lifecycle metrics from its activity log and messages.
+///
+/// The report time is the ticket's `createdAt`, falling back to the
+/// earliest activity. The status before the first recorded change is that
+/// change's old value (or the ticket's current status when it never
+/// changed).
+pub fn ticket_metrics(
+    ticket: &Ticket,
+    activity: &[ActivityLog],
+    messages: &[Message],
+    now: DateTime<Utc>,
+) -> TicketMetrics {
+    let created = ticket
+        .created_at
+        .as_deref()
+        .and_then(parse_time)
+        .or_else(|| activity.iter().filter_map(ActivityLog::time).min());
+
+    let first_response = messages
+        .iter()
+        .filter(|message| message.is_team_reply())
+        .filter_map(|message| message.created_at.as_deref().and_then(parse_time))
+        .min();
+
+    let mut changes: Vec<StatusChange> = activity
+        .iter()
+        .filter(|log| log.is_status_change())
+        .filter_map(|log| {
+            Some(StatusChange {
+                at: log.time()?,
+                from: log.old_value.as_ref().and_then(|v| v.as_str()),
+                to: log.new_value.as_ref()?.as_str()?,
+            })
+        })
+        .collect();
+    changes.sort_by_key(|change| change.at);
+
+    let done = TicketStatus::Done.as_str();
+    let is_done = |status: &str| status.eq_ignore_ascii_case(done);
+
+    let mut status = changes
+        .first()
+        .and_then(|change| change.from)
+        .or(ticket.status.map(|s| s.as_str()))
+        .unwrap_or(TicketStatus::Open.as_str());
+    let mut since = created;
+    let mut time_in_status = Vec::new();
+    let mut reopen_count = 0;
+    let mut resolved = None;
+
+    for change in &changes {
+        if let Some(since) = since {
+            add_time(&mut time_in_status, status, change.at - since);
+        }
+        if is_done(status) && !is_done(change.to) {
+            reopen_count += 1;
+        }
+        if is_done(change.to) && !is_done(status) {
+            resolved = Some(change.at);
+        }
+        status = change.to;
+        since = Some(change.at);
+    }
+    // A ticket that was reopened and is still open isn't resolved.
+    if !is_done(status) {
+        resolved = None;
+        if let Some(since) = since {
+            add_time(&mut time_in_status, status, now - since);
+        }
+    }
+
+    TicketMetrics {
+        created_at: created.map(format_time),
+        first_response_at: first_response.map(format_time),
+        time_to_first_response_secs: elapsed(created, first_response),
+        resolved_at: resolved.map(format_time),
+        time_to_resolution_secs: elapsed(created, resolved),
+        time_in_status,
+        reopen_count,
+    }
+}
+
+fn add_time(totals: &mut Vec<StatusTime>, status: &str, duration: chrono::TimeDelta) {
+    let secs = duration.num_seconds().max(0);
+    match totals
+        .iter_mut()
+        .find(|t| t.status.eq_ignore_ascii_case(status))
+    {
+        Some(total) => total.secs += secs,
+        None => totals.push(StatusTime {
+            status: status.to_string(),
+            secs,
+        }),
+    }
+}
+
+fn elapsed(from: Option<DateTime<Utc>>, to: Option<DateTime<Utc>>) -> Option<i64> {
+    Some((to? - from?).num_seconds().max(0))
+}
+
+fn parse_time(value: &str) -> Option<DateTime<Utc>> {
+    DateTime::parse_from_rfc3339(value).ok().map(|t| t.to_utc())
+}
+
+fn format_time(time: DateTime<Utc>) -> String {
+    time.to_rfc3339_opts(SecondsFormat::Secs, true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn now() -> DateTime<Utc> {
+        parse_time("2026-03-10T00:00:00Z").unwrap()
+    }
+
+    fn ticket(status: &str) -> Ticket {
+        serde_json::from_value(json!({
+            "id": "t1",
+            "status": status,
+            "createdAt": "2026-03-01T10:00:00Z"
+        }))
+        .unwrap()
+    }
+
+    fn activity() -> Vec<ActivityLog> {
+        serde_json::from_value(json!([
+            { "type": "CREATED", "createdAt": "2026-03-01T10:00:00Z" },
+            { "type": "STATUS_CHANGED", "field": "status", "oldValue": "OPEN", "newValue": "INPROGRESS", "createdAt": "2026-03-01T11:00:00Z" },
+            { "type": "PRIORITY_CHANGED", "field": "priority", "oldValue": "LOW", "newValue": "HIGH", "createdAt": "2026-03-01T11:30:00Z" },
+            { "type": "STATUS_CHANGED", "field": "status", "oldValue": "INPROGRESS", "newValue": "DONE", "createdAt": "2026-03-02T10:00:00Z" },
+            { "type": "STATUS_CHANGED", "field": "status", "oldValue": "DONE", "newValue": "OPEN", "createdAt": "2026-03-03T10:00:00Z" },
+            { "type": "STATUS_CHANGED", "field": "status", "oldValue": "OPEN", "newValue": "DONE", "createdAt": "2026-03-03T12:00:00Z" }
+        ]))
+        .unwrap()
+    }
+
+    fn messages() -> Vec<Message> {
+        serde_json::from_value(json!([
+            { "id": "m1", "type": "USER_TEXT", "session": { "id": "s1" }, "createdAt": "2026-03-01T10:00:00Z" },
+            { "id": "m2", "type": "NOTE", "user": { "id": "u1" }, "createdAt": "2026-03-01T10:10:00Z" },
+            { "id": "m3", "type": "BOT_REPLY", "bot": true, "createdAt": "2026-03-01T10:20:00Z" },
+            { "id": "m4", "type": "TEXT", "user": { "id": "u1" }, "createdAt": "2026-03-01T10:30:00Z" }
+        ]))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_resolved_ticket_metrics() {
+        let metrics = ticket_metrics(&ticket("DONE"), &activity(), &messages(), now());
+
+        assert_eq!(
+            metrics.first_response_at.as_deref(),
+            Some("2026-03-01T10:30:00Z")
+        );
+        assert_eq!(metrics.time_to_first_response_secs, Some(30 * 60));
+        assert_eq!(metrics.resolved_at.as_deref(), Some("2026-03-03T12:00:00Z"));
+        assert_eq!(
+            metrics.time_to_resolution_secs,
+            Some(2 * 86_400 + 2 * 3_600)
+        );
+        assert_eq!(metrics.reopen_count, 1);
+        assert_eq!(
+            metrics.time_in_status,
+            vec![
+                StatusTime {
+                    status: "OPEN".into(),
+                    secs: 3 * 3_600
+                },
+                StatusTime {
+                    status: "INPROGRESS".into(),
+                    secs: 23 * 3_600
+                },
+                StatusTime {
+                    status: "DONE".into(),
+                    secs: 86_400
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_open_ticket_counts_until_now() {
+        let activity: Vec<ActivityLog> = activity().into_iter().take(2).collect();
+        let metrics = ticket_metrics(&ticket("INPROGRESS"), &activity, &[], now());
+
+        assert_eq!(metrics.first_response_at, None);
+        assert_eq!(metrics.time_to_first_response_secs, None);
+        assert_eq!(metrics.resolved_at, None);
+        assert_eq!(metrics.reopen_count, 0);
+        assert_eq!(
+            metrics.time_in_status,
+            vec![
+                StatusTime {
+                    status: "OPEN".into(),
+                    secs: 3_600
+                },
+                // From 2026-03-01T11:00 until now.
+                StatusTime {
+                    status: "INPROGRESS".into(),
+                    secs: 8 * 86_400 + 13 * 3_600
+                },
+            ]
+        );
+    }
+}
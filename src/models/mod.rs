@@ -1,2 +1,5 @@
+pub mod fields;
+pub mod logs;
 pub mod message;
+pub mod metrics;
 pub mod ticket;
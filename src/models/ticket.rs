@@ -1,6 +1,8 @@
+use std::fmt;
+
 use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "SCREAMING_SNAKE_CASE")]
 pub enum TicketType {
     Bug,
@@ -12,7 +14,33 @@ pub enum TicketType {
     Unknown,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+impl TicketType {
+    /// Every variant the API documents (all but `Unknown`).
+    pub const KNOWN: [TicketType; 4] = [
+        TicketType::Bug,
+        TicketType::FeatureRequest,
+        TicketType::Inquiry,
+        TicketType::Bot,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            TicketType::Bug => "BUG",
+            TicketType::FeatureRequest => "FEATURE_REQUEST",
+            TicketType::Inquiry => "INQUIRY",
+            TicketType::Bot => "BOT",
+            TicketType::Unknown => "UNKNOWN",
+        }
+    }
+}
+
+impl fmt::Display for TicketType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "SCREAMING_SNAKE_CASE")]
 pub enum TicketStatus {
     Open,
@@ -23,7 +51,31 @@ pub enum TicketStatus {
     Unknown,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+impl TicketStatus {
+    /// Every variant the API documents (all but `Unknown`).
+    pub const KNOWN: [TicketStatus; 3] = [
+        TicketStatus::Open,
+        TicketStatus::InProgress,
+        TicketStatus::Done,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            TicketStatus::Open => "OPEN",
+            TicketStatus::InProgress => "INPROGRESS",
+            TicketStatus::Done => "DONE",
+            TicketStatus::Unknown => "UNKNOWN",
+        }
+    }
+}
+
+impl fmt::Display for TicketStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "SCREAMING_SNAKE_CASE")]
 pub enum TicketPriority {
     Low,
@@ -33,6 +85,30 @@ pub enum TicketPriority {
     Unknown,
 }
 
+impl TicketPriority {
+    /// Every variant the API documents (all but `Unknown`).
+    pub const KNOWN: [TicketPriority; 3] = [
+        TicketPriority::Low,
+        TicketPriority::Medium,
+        TicketPriority::High,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            TicketPriority::Low => "LOW",
+            TicketPriority::Medium => "MEDIUM",
+            TicketPriority::High => "HIGH",
+            TicketPriority::Unknown => "UNKNOWN",
+        }
+    }
+}
+
+impl fmt::Display for TicketPriority {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Ticket {
     pub id: String,
@@ -105,6 +181,23 @@ pub struct UserRef {
     pub last_name: Option<String>,
 }
 
+impl UserRef {
+    /// Full name if known, otherwise the email address.
+    pub fn display_name(&self) -> Option<String> {
+        let name = [self.first_name.as_deref(), self.last_name.as_deref()]
+            .into_iter()
+            .flatten()
+            .filter(|part| !part.is_empty())
+            .collect::<Vec<_>>()
+            .join(" ");
+        if name.is_empty() {
+            self.email.clone()
+        } else {
+            Some(name)
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SessionRef {
     #[serde(default)]
@@ -117,6 +210,16 @@ pub struct SessionRef {
     pub name: Option<String>,
 }
 
+impl SessionRef {
+    /// Contact name if known, otherwise the email address.
+    pub fn display_name(&self) -> Option<String> {
+        self.name
+            .clone()
+            .filter(|name| !name.is_empty())
+            .or_else(|| self.email.clone())
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TicketListResponse {
     pub tickets: Vec<Ticket>,
@@ -128,14 +231,135 @@ pub struct TicketListResponse {
     pub total_count: Option<u64>,
 }
 
+/// Body for `POST /tickets`.
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct CreateTicketRequest {
+    pub title: String,
+
+    #[serde(rename = "type", skip_serializing_if = "Option::is_none")]
+    pub ticket_type: Option<TicketType>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub status: Option<TicketStatus>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub priority: Option<TicketPriority>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+
+    /// Session ID of the reporting user.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub session: Option<String>,
+
+    #[serde(rename = "formData", skip_serializing_if = "Option::is_none")]
+    pub form_data: Option<serde_json::Map<String, serde_json::Value>>,
+
+    #[serde(rename = "customData", skip_serializing_if = "Option::is_none")]
+    pub custom_data: Option<serde_json::Map<String, serde_json::Value>>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tags: Option<Vec<String>>,
+}
+
+/// Body for `PATCH /tickets/{id}`. Only the fields that are set are sent.
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct UpdateTicketRequest {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+
+    #[serde(rename = "type", skip_serializing_if = "Option::is_none")]
+    pub ticket_type: Option<TicketType>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub status: Option<TicketStatus>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub priority: Option<TicketPriority>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+
+    /// Assignee user ID; `Some(None)` unassigns the ticket.
+    #[serde(rename = "processingUser", skip_serializing_if = "Option::is_none")]
+    pub processing_user: Option<Option<String>>,
+
+    #[serde(rename = "processingTeam", skip_serializing_if = "Option::is_none")]
+    pub processing_team: Option<String>,
+
+    /// Replaces the ticket's tags.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tags: Option<Vec<String>>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub archived: Option<bool>,
+
+    #[serde(rename = "isSpam", skip_serializing_if = "Option::is_none")]
+    pub is_spam: Option<bool>,
+
+    /// Replaces the ticket's custom data.
+    #[serde(rename = "customData", skip_serializing_if = "Option::is_none")]
+    pub custom_data: Option<serde_json::Map<String, serde_json::Value>>,
+
+    /// Replaces the ticket's form data.
+    #[serde(rename = "formData", skip_serializing_if = "Option::is_none")]
+    pub form_data: Option<serde_json::Map<String, serde_json::Value>>,
+
+    /// Any other fields to send as-is (the endpoint accepts additional
+    /// properties).
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+impl UpdateTicketRequest {
+    /// True if the request would not change anything.
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+/// A value for an enum filter. `Other` is sent to the API unchecked, for
+/// values the server accepts but this crate does not model yet.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FilterValue<T> {
+    Known(T),
+    Other(String),
+}
+
+impl<T> From<T> for FilterValue<T> {
+    fn from(value: T) -> Self {
+        FilterValue::Known(value)
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for FilterValue<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FilterValue::Known(value) => value.fmt(f),
+            FilterValue::Other(value) => f.write_str(value),
+        }
+    }
+}
+
+/// Ticket list filters. Each enum filter matches any of its values; an
+/// empty list leaves that field unfiltered.
 #[derive(Debug, Clone, Default)]
 pub struct TicketFilters {
-    pub status: Option<String>,
-    pub ticket_type: Option<String>,
-    pub priority: Option<String>,
+    pub status: Vec<FilterValue<TicketStatus>>,
+    pub ticket_type: Vec<FilterValue<TicketType>>,
+    pub priority: Vec<FilterValue<TicketPriority>>,
+    /// Sent only when set. Documented for listing, not for search.
     pub archived: Option<bool>,
+    /// Sent only when set. Documented for listing, not for search.
     pub is_spam: Option<bool>,
     pub sort: Option<String>,
     pub limit: Option<u64>,
     pub skip: Option<u64>,
 }
+
+/// Full-text ticket search, narrowed by the same filters as listing.
+#[derive(Debug, Clone, Default)]
+pub struct TicketSearch {
+    pub query: String,
+    pub filters: TicketFilters,
+}
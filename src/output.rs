@@ -0,0 +1,299 @@
+use std::io::{IsTerminal, Write};
+
+use clap::ValueEnum;
+
+use gleap::client::Redaction;
+use gleap::client::RequestPreview;
+use gleap::error::AppError;
+use gleap::models::logs::{ConsoleLog, NetworkLog};
+use gleap::models::message::Message;
+use gleap::models::ticket::{Ticket, TicketListResponse};
+use gleap::render::delimited::{self, Delimiter};
+use gleap::render::logs::ConsoleOptions;
+use gleap::render::timeline::Timeline;
+use gleap::render::transcript::Transcript;
+use gleap::render::{self, columns, table};
+use serde::Serialize;
+
+use crate::cli::shared::{Format, OutputFormat};
+
+/// Prints command results in the format selected by `--format`.
+///
+/// Commands that only ever produce JSON (create, update, activity logs) go through
+/// [`Output::json`]; list and detail views pick their rendering here.
+pub struct Output {
+    format: Format,
+    requested: Option<Format>,
+    columns: Option<Vec<String>>,
+}
+
+impl Output {
+    /// Use the requested format, or table on a terminal and JSON otherwise.
+    pub fn new(args: &OutputFormat) -> Self {
+        let format = args.format.unwrap_or_else(|| {
+            if std::io::stdout().is_terminal() {
+                Format::Table
+            } else {
+                Format::Json
+            }
+        });
+        Self {
+            format,
+            requested: args.format,
+            columns: args.columns.clone(),
+        }
+    }
+
+    pub fn tickets(&self, response: &TicketListResponse) -> Result<(), AppError> {
+        match self.format {
+            Format::Json => self.json(response),
+            Format::Jsonl => json_lines(&response.tickets),
+            Format::Table => {
+                let table = match &self.columns {
+                    Some(columns) => custom_table(&response.tickets, columns)?,
+                    None => table::ticket_table(&response.tickets, chrono::Utc::now()),
+                };
+                if table.is_empty() {
+                    eprintln!("No tickets found.");
+                    return Ok(());
+                }
+                print!("{}", table.render(terminal_width()));
+                if let Some(total) = response.total_count
+                    && total > response.tickets.len() as u64
+                {
+                    eprintln!("Showing {} of {} tickets", response.tickets.len(), total);
+                }
+                Ok(())
+            }
+            Format::Csv | Format::Tsv => self.delimited(&response.tickets, columns::TICKET_COLUMNS),
+            Format::Markdown | Format::Text | Format::Html | Format::Har => Err(self.unsupported()),
+        }
+    }
+
+    pub fn ticket(&self, ticket: &Ticket) -> Result<(), AppError> {
+        match self.format {
+            Format::Json => self.json(ticket),
+            Format::Jsonl => json_lines(std::slice::from_ref(ticket)),
+            Format::Table => match &self.columns {
+                Some(columns) => {
+                    let table = custom_table(std::slice::from_ref(ticket), columns)?;
+                    print!("{}", table.render(terminal_width()));
+                    Ok(())
+                }
+                None => {
+                    print!("{}", table::ticket_detail(ticket, chrono::Utc::now()));
+                    Ok(())
+                }
+            },
+            Format::Csv | Format::Tsv => {
+                self.delimited(std::slice::from_ref(ticket), columns::TICKET_COLUMNS)
+            }
+            Format::Markdown | Format::Text | Format::Html | Format::Har => Err(self.unsupported()),
+        }
+    }
+
+    pub fn messages(&self, messages: &[Message]) -> Result<(), AppError> {
+        match self.format {
+            Format::Json => self.json(&messages),
+            Format::Jsonl => json_lines(messages),
+            Format::Table => {
+                let table = match &self.columns {
+                    Some(columns) => custom_table(&message_values(messages)?, columns)?,
+                    None => table::message_table(messages, chrono::Utc::now()),
+                };
+                if table.is_empty() {
+                    eprintln!("No messages found.");
+                    return Ok(());
+                }
+                print!("{}", table.render(terminal_width()));
+                Ok(())
+            }
+            Format::Csv | Format::Tsv => {
+                self.delimited(&message_values(messages)?, columns::MESSAGE_COLUMNS)
+            }
+            Format::Markdown | Format::Text | Format::Html | Format::Har => Err(self.unsupported()),
+        }
+    }
+
+    /// Console logs print as a log view (colored on a terminal) for table
+    /// and text output.
+    pub fn console_logs(&self, logs: &[ConsoleLog], full_stack: bool) -> Result<(), AppError> {
+        match self.format {
+            Format::Json => self.json(logs),
+            Format::Jsonl => json_lines(logs),
+            Format::Table | Format::Text => {
+                if logs.is_empty() {
+                    eprintln!("No console logs found.");
+                    return Ok(());
+                }
+                let options = ConsoleOptions {
+                    color: self.format == Format::Table && use_color(),
+                    full_stack,
+                };
+                print!("{}", render::logs::console_view(logs, options));
+                Ok(())
+            }
+            Format::Csv | Format::Tsv => self.delimited(logs, columns::CONSOLE_COLUMNS),
+            Format::Markdown | Format::Html | Format::Har => Err(self.unsupported()),
+        }
+    }
+
+    /// Network logs print as a request summary for table output; `har`
+    /// exports them as an HTTP Archive.
+    /// `redaction` applies to the headers in HAR output.
+    pub fn network_logs(&self, logs: &[NetworkLog], redaction: &Redaction) -> Result<(), AppError> {
+        match self.format {
+            Format::Json => self.json(logs),
+            Format::Jsonl => json_lines(logs),
+            Format::Har => self.json(&render::har::to_har(logs, redaction)),
+            Format::Table => {
+                let table = match &self.columns {
+                    Some(columns) => custom_table(logs, columns)?,
+                    None => table::network_table(logs),
+                };
+                if table.is_empty() {
+                    eprintln!("No network logs found.");
+                    return Ok(());
+                }
+                print!("{}", table.render(terminal_width()));
+                Ok(())
+            }
+            Format::Csv | Format::Tsv => self.delimited(logs, columns::NETWORK_COLUMNS),
+            Format::Markdown | Format::Text | Format::Html => Err(self.unsupported()),
+        }
+    }
+
+    /// Timelines print as an event table followed by the metrics for table
+    /// and text output.
+    pub fn timeline(&self, timeline: &Timeline) -> Result<(), AppError> {
+        match self.format {
+            Format::Json | Format::Jsonl => self.json(timeline),
+            Format::Table | Format::Text => {
+                print!("{}", timeline.to_text(terminal_width()));
+                Ok(())
+            }
+            Format::Csv | Format::Tsv | Format::Markdown | Format::Html | Format::Har => {
+                Err(self.unsupported())
+            }
+        }
+    }
+
+    /// Transcripts default to Markdown rather than following the terminal
+    /// check, since they are documents rather than listings.
+    pub fn transcript(&self, transcript: &Transcript) -> Result<(), AppError> {
+        match self.requested.unwrap_or(Format::Markdown) {
+            Format::Markdown => print!("{}", transcript.to_markdown()),
+            Format::Text => print!("{}", transcript.to_text()),
+            Format::Html => print!("{}", transcript.to_html()),
+            Format::Json => return self.json(transcript),
+            Format::Jsonl => return json_lines(&transcript.entries),
+            Format::Table | Format::Csv | Format::Tsv | Format::Har => {
+                return Err(self.unsupported());
+            }
+        }
+        Ok(())
+    }
+
+    /// Print a request that a dry run didn't send: as JSON for the JSON
+    /// formats, or as an HTTP message.
+    pub fn request_preview(&self, preview: &RequestPreview) -> Result<(), AppError> {
+        match self.format {
+            Format::Json | Format::Jsonl => self.json(preview)?,
+            _ => print!("{}", preview.to_text()),
+        }
+        eprintln!("Dry run: request not sent.");
+        Ok(())
+    }
+
+    /// Whether results can be printed page by page as they arrive, rather
+    /// than collected first (only JSON Lines has no document framing).
+    pub fn streams(&self) -> bool {
+        self.format == Format::Jsonl
+    }
+
+    /// Print items as JSON Lines (see [`Output::streams`]).
+    pub fn lines<T: Serialize>(&self, items: &[T]) -> Result<(), AppError> {
+        json_lines(items)
+    }
+
+    /// Print any serializable value as JSON: compact on one line for
+    /// `--format jsonl`, pretty-printed for every other format.
+    pub fn json<T: Serialize + ?Sized>(&self, value: &T) -> Result<(), AppError> {
+        if self.format == Format::Jsonl {
+            let mut stdout = std::io::stdout().lock();
+            serde_json::to_writer(&mut stdout, value)?;
+            writeln!(stdout)?;
+            return Ok(());
+        }
+        let json = serde_json::to_string_pretty(value)?;
+        println!("{}", json);
+        Ok(())
+    }
+
+    fn unsupported(&self) -> AppError {
+        let name = self
+            .format
+            .to_possible_value()
+            .map(|v| v.get_name().to_string())
+            .unwrap_or_default();
+        AppError::Config(format!("--format {name} is not supported by this command"))
+    }
+
+    fn delimited<T: Serialize>(&self, items: &[T], defaults: &[&str]) -> Result<(), AppError> {
+        let columns = self
+            .columns
+            .clone()
+            .unwrap_or_else(|| defaults.iter().map(|c| c.to_string()).collect());
+        let delimiter = match self.format {
+            Format::Tsv => Delimiter::Tsv,
+            _ => Delimiter::Csv,
+        };
+        let rows = columns::project(items, &columns)?;
+        print!("{}", delimited::to_string(&columns, &rows, delimiter));
+        Ok(())
+    }
+}
+
+/// Write one compact JSON object per line, flushing after each so
+/// consumers see items as soon as they are printed. A closed pipe (e.g.
+/// `| head`) ends output quietly rather than as an error.
+fn json_lines<T: Serialize>(items: &[T]) -> Result<(), AppError> {
+    let mut stdout = std::io::stdout().lock();
+    for item in items {
+        let line = serde_json::to_string(item)?;
+        match writeln!(stdout, "{line}").and_then(|_| stdout.flush()) {
+            Ok(()) => {}
+            Err(e) if e.kind() == std::io::ErrorKind::BrokenPipe => return Ok(()),
+            Err(e) => return Err(e.into()),
+        }
+    }
+    Ok(())
+}
+
+/// Messages as exported to columns, with their content as plain `text`.
+fn message_values(messages: &[Message]) -> Result<Vec<serde_json::Value>, AppError> {
+    Ok(messages
+        .iter()
+        .map(columns::message_value)
+        .collect::<Result<_, _>>()?)
+}
+
+/// A table over user-selected field paths, headed by the paths themselves.
+fn custom_table<T: Serialize>(items: &[T], columns: &[String]) -> Result<table::Table, AppError> {
+    let headers: Vec<String> = columns.iter().map(|c| c.to_uppercase()).collect();
+    let headers: Vec<&str> = headers.iter().map(String::as_str).collect();
+    let mut table = table::Table::new(&headers);
+    for row in columns::project(items, columns)? {
+        table.push_row(row);
+    }
+    Ok(table)
+}
+
+/// Color terminal output unless `NO_COLOR` is set (https://no-color.org).
+fn use_color() -> bool {
+    std::io::stdout().is_terminal() && std::env::var_os("NO_COLOR").is_none_or(|v| v.is_empty())
+}
+
+fn terminal_width() -> Option<usize> {
+    terminal_size::terminal_size().map(|(width, _)| width.0 as usize)
+}
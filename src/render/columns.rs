@@ -0,0 +1,161 @@
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::models::message::Message;
+
+use super::richtext;
+
+/// Default columns for ticket exports, as paths into the serialized `Ticket`.
+pub const TICKET_COLUMNS: &[&str] = &[
+    "id",
+    "title",
+    "status",
+    "priority",
+    "type",
+    "createdAt",
+    "processingUser.email",
+];
+
+/// Default columns for message exports, as paths into [`message_value`].
+/// `text` is the content as plain text; `data.content` still selects the
+/// raw content when asked for.
+pub const MESSAGE_COLUMNS: &[&str] = &["id", "type", "createdAt", "user.email", "text"];
+
+/// Default columns for console log exports, as paths into the serialized `ConsoleLog`.
+pub const CONSOLE_COLUMNS: &[&str] = &["date", "priority", "log"];
+
+/// Default columns for network log exports, as paths into the serialized `NetworkLog`.
+pub const NETWORK_COLUMNS: &[&str] = &["date", "type", "url", "response.status", "duration"];
+
+/// A message as serialized, plus a `text` field holding its content as
+/// plain text (unless the API already sent a `text` field).
+pub fn message_value(message: &Message) -> Result<Value, serde_json::Error> {
+    let mut value = serde_json::to_value(message)?;
+    if let Value::Object(map) = &mut value
+        && !map.contains_key("text")
+    {
+        let content = lookup(map.get("data").unwrap_or(&Value::Null), "content");
+        let text = content.map(richtext::to_plain_text).unwrap_or_default();
+        map.insert("text".into(), Value::String(text));
+    }
+    Ok(value)
+}
+
+/// Resolve a dotted path (e.g. `processingUser.email`, `customData.plan`,
+/// `tags.0`) against a JSON value. Path segments use the API's field names,
+/// so fields captured in a model's flattened `extra` map are reachable too.
+pub fn lookup<'a>(value: &'a Value, path: &str) -> Option<&'a Value> {
+    path.split('.')
+        .try_fold(value, |current, segment| match current {
+            Value::Object(map) => map.get(segment),
+            Value::Array(items) => segment.parse::<usize>().ok().and_then(|i| items.get(i)),
+            _ => None,
+        })
+}
+
+/// Flatten a JSON value into a single cell. Missing and null values are
+/// empty, arrays of scalars are comma-joined, and anything else nested is
+/// written as compact JSON.
+pub fn cell(value: Option<&Value>) -> String {
+    match value {
+        None | Some(Value::Null) => String::new(),
+        Some(Value::String(s)) => s.clone(),
+        Some(Value::Bool(b)) => b.to_string(),
+        Some(Value::Number(n)) => n.to_string(),
+        Some(Value::Array(items)) if items.iter().all(is_scalar) => items
+            .iter()
+            .map(|item| cell(Some(item)))
+            .collect::<Vec<_>>()
+            .join(", "),
+        Some(other) => other.to_string(),
+    }
+}
+
+/// Project each item onto the given column paths.
+pub fn project<T: Serialize>(
+    items: &[T],
+    columns: &[String],
+) -> Result<Vec<Vec<String>>, serde_json::Error> {
+    items
+        .iter()
+        .map(|item| {
+            let value = serde_json::to_value(item)?;
+            Ok(columns
+                .iter()
+                .map(|column| cell(lookup(&value, column)))
+                .collect())
+        })
+        .collect()
+}
+
+fn is_scalar(value: &Value) -> bool {
+    !matches!(value, Value::Array(_) | Value::Object(_))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::ticket::Ticket;
+    use serde_json::json;
+
+    #[test]
+    fn test_lookup_nested_and_flattened_fields() {
+        let ticket: Ticket = serde_json::from_value(json!({
+            "id": "t1",
+            "processingUser": { "email": "ann@example.com" },
+            "customData": { "plan": "pro" },
+            "tags": ["ui", "safari"],
+            "shareToken": "abc"
+        }))
+        .unwrap();
+
+        let rows = project(
+            &[ticket],
+            &[
+                "id".into(),
+                "processingUser.email".into(),
+                "customData.plan".into(),
+                "tags".into(),
+                "tags.1".into(),
+                "shareToken".into(),
+                "session.email".into(),
+            ],
+        )
+        .unwrap();
+
+        assert_eq!(
+            rows,
+            vec![vec![
+                "t1",
+                "ann@example.com",
+                "pro",
+                "ui, safari",
+                "safari",
+                "abc",
+                ""
+            ]]
+        );
+    }
+
+    #[test]
+    fn test_message_text_column() {
+        let message: Message = serde_json::from_value(json!({
+            "id": "m1",
+            "data": { "content": { "type": "doc", "content": [
+                { "type": "paragraph", "content": [{ "type": "text", "text": "Fixed in 2.3" }] }
+            ]}}
+        }))
+        .unwrap();
+        let value = message_value(&message).unwrap();
+
+        assert_eq!(cell(lookup(&value, "text")), "Fixed in 2.3");
+        assert!(cell(lookup(&value, "data.content")).starts_with(r#"{"content":"#));
+    }
+
+    #[test]
+    fn test_cell_nested_object_is_compact_json() {
+        let value = json!({ "account": { "id": 42 } });
+        assert_eq!(cell(lookup(&value, "account")), r#"{"id":42}"#);
+        assert_eq!(cell(lookup(&value, "account.id")), "42");
+    }
+}
@@ -0,0 +1,145 @@
+//! Turn captured network requests into `curl` commands that replay them.
+//!
+//! Credentials are redacted before anything is printed: the configured
+//! headers keep their name (and, for `Authorization`, the scheme) so the
+//! command shows where to put a local token, but never the customer's.
+
+use crate::client::Redaction;
+use crate::models::logs::{NetworkLog, body_text};
+
+/// Headers curl computes itself; replaying a captured value would be wrong
+/// as soon as the body is edited.
+const SKIPPED_HEADERS: &[&str] = &["content-length"];
+
+/// A multi-line `curl` command for the request, with one option per line.
+/// The URL is passed with `--url`, so a captured "URL" starting with `-`
+/// can't be read as an option.
+pub fn to_curl(log: &NetworkLog, redaction: &Redaction) -> String {
+    let method = log.method.as_deref().unwrap_or("GET").to_ascii_uppercase();
+    let body = log
+        .request
+        .as_ref()
+        .and_then(|r| r.body.as_ref())
+        .filter(|body| !body.is_null())
+        .map(body_text);
+
+    let mut parts = vec![format!(
+        "curl --url {}",
+        quote(log.url.as_deref().unwrap_or(""))
+    )];
+    // curl sends GET, or POST when given a body, unless told otherwise.
+    let implied = if body.is_some() { "POST" } else { "GET" };
+    if method != implied {
+        parts.push(format!("-X {}", quote(&method)));
+    }
+    if let Some(headers) = log.request.as_ref().and_then(|r| r.headers.as_ref()) {
+        for (name, value) in headers {
+            if SKIPPED_HEADERS.iter().any(|h| h.eq_ignore_ascii_case(name)) {
+                continue;
+            }
+            let value = redaction.header_value(name, &body_text(value));
+            parts.push(format!("-H {}", quote(&format!("{name}: {value}"))));
+        }
+    }
+    if let Some(body) = body {
+        parts.push(format!("--data-raw {}", quote(&body)));
+    }
+    parts.join(" \\\n  ")
+}
+
+/// A shell comment line holding `text`. Line breaks and other control
+/// characters are escaped, so captured text can't end the comment and run
+/// as a command.
+pub fn comment(text: &str) -> String {
+    let mut line = String::from("# ");
+    for ch in text.chars() {
+        if ch.is_control() {
+            line.extend(ch.escape_default());
+        } else {
+            line.push(ch);
+        }
+    }
+    line
+}
+
+/// Single-quote a word for POSIX shells, unless it's plainly safe as is.
+fn quote(text: &str) -> String {
+    let safe = !text.is_empty()
+        && text
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || "-_./:@%+,=".contains(c));
+    if safe {
+        text.to_string()
+    } else {
+        format!("'{}'", text.replace('\'', r"'\''"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn log(value: serde_json::Value) -> NetworkLog {
+        serde_json::from_value(value).unwrap()
+    }
+
+    #[test]
+    fn test_post_with_redacted_headers() {
+        let login = log(json!({
+            "type": "POST",
+            "url": "https://api.example.com/login",
+            "request": {
+                "headers": {
+                    "Authorization": "Bearer secret",
+                    "Content-Length": "27",
+                    "Cookie": "sid=abc; theme=dark",
+                    "X-Api-Key": "k-123"
+                },
+                "payload": { "user": "o'brien" }
+            }
+        }));
+
+        assert_eq!(
+            to_curl(&login, &Redaction::new(&["x-api-key"])),
+            "curl --url https://api.example.com/login \\\n  \
+             -H 'Authorization: Bearer REDACTED' \\\n  \
+             -H 'Cookie: sid=REDACTED; theme=REDACTED' \\\n  \
+             -H 'X-Api-Key: REDACTED' \\\n  \
+             --data-raw '{\"user\":\"o'\\''brien\"}'"
+        );
+    }
+
+    #[test]
+    fn test_method_only_when_not_implied() {
+        let get = log(json!({ "type": "get", "url": "https://example.com/a?b=1" }));
+        assert_eq!(
+            to_curl(&get, &Redaction::default()),
+            "curl --url 'https://example.com/a?b=1'"
+        );
+
+        let delete = log(json!({ "type": "DELETE", "url": "https://example.com/a" }));
+        assert_eq!(
+            to_curl(&delete, &Redaction::default()),
+            "curl --url https://example.com/a \\\n  -X DELETE"
+        );
+
+        let put = log(
+            json!({ "type": "PUT", "url": "https://example.com/a", "request": { "payload": "x" } }),
+        );
+        assert!(to_curl(&put, &Redaction::default()).contains("-X PUT"));
+    }
+
+    #[test]
+    fn test_captured_text_cannot_escape() {
+        let option = log(json!({ "url": "-o/etc/x" }));
+        assert_eq!(
+            to_curl(&option, &Redaction::default()),
+            "curl --url -o/etc/x"
+        );
+        assert_eq!(
+            comment("[0] GET https://a.example/\nrm -rf ~ → 200"),
+            "# [0] GET https://a.example/\\nrm -rf ~ → 200"
+        );
+    }
+}
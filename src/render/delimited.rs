@@ -0,0 +1,136 @@
+use std::borrow::Cow;
+
+/// Delimited text flavours for spreadsheet export.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Delimiter {
+    /// RFC 4180 CSV: fields containing commas, quotes or line breaks are
+    /// quoted, with embedded quotes doubled.
+    Csv,
+    /// Tab-separated values: tabs, line breaks and backslashes inside fields
+    /// are escaped as `\t`, `\n`, `\r` and `\\` so every record is one line.
+    Tsv,
+}
+
+/// Render a header row followed by data rows.
+///
+/// Fields come from customer-written content and end up in spreadsheets, so
+/// a field that a spreadsheet would run as a formula (starting with `=`,
+/// `+`, `-`, `@`, a tab or a carriage return) is prefixed with `'` to keep
+/// it text. Plain numbers such as `-12.5` are left alone.
+pub fn to_string<H: AsRef<str>>(
+    headers: &[H],
+    rows: &[Vec<String>],
+    delimiter: Delimiter,
+) -> String {
+    let mut out = String::new();
+    write_record(&mut out, headers.iter().map(AsRef::as_ref), delimiter);
+    for row in rows {
+        write_record(&mut out, row.iter().map(String::as_str), delimiter);
+    }
+    out
+}
+
+fn write_record<'a>(out: &mut String, fields: impl Iterator<Item = &'a str>, delimiter: Delimiter) {
+    for (i, field) in fields.enumerate() {
+        if i > 0 {
+            out.push(match delimiter {
+                Delimiter::Csv => ',',
+                Delimiter::Tsv => '\t',
+            });
+        }
+        let field = defuse_formula(field);
+        match delimiter {
+            Delimiter::Csv => write_csv_field(out, &field),
+            Delimiter::Tsv => write_tsv_field(out, &field),
+        }
+    }
+    match delimiter {
+        Delimiter::Csv => out.push_str("\r\n"),
+        Delimiter::Tsv => out.push('\n'),
+    }
+}
+
+fn defuse_formula(field: &str) -> Cow<'_, str> {
+    let trigger = field.starts_with(['=', '+', '-', '@', '\t', '\r']);
+    if trigger && field.parse::<f64>().is_err() {
+        Cow::Owned(format!("'{field}"))
+    } else {
+        Cow::Borrowed(field)
+    }
+}
+
+fn write_csv_field(out: &mut String, field: &str) {
+    if field.contains([',', '"', '\n', '\r']) {
+        out.push('"');
+        out.push_str(&field.replace('"', "\"\""));
+        out.push('"');
+    } else {
+        out.push_str(field);
+    }
+}
+
+fn write_tsv_field(out: &mut String, field: &str) {
+    for ch in field.chars() {
+        match ch {
+            '\\' => out.push_str("\\\\"),
+            '\t' => out.push_str("\\t"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            ch => out.push(ch),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rows() -> Vec<Vec<String>> {
+        vec![
+            vec!["t1".into(), "plain".into()],
+            vec![
+                "t2".into(),
+                "has, comma and \"quotes\"\nand a newline".into(),
+            ],
+            vec!["t3".into(), String::new()],
+        ]
+    }
+
+    #[test]
+    fn test_csv_quoting() {
+        assert_eq!(
+            to_string(&["id", "title"], &rows(), Delimiter::Csv),
+            "id,title\r\nt1,plain\r\nt2,\"has, comma and \"\"quotes\"\"\nand a newline\"\r\nt3,\r\n"
+        );
+    }
+
+    #[test]
+    fn test_formulas_are_defused() {
+        let rows = vec![vec![
+            "=HYPERLINK(\"http://evil\",\"x\")".into(),
+            "+1+2".into(),
+            "@SUM(A1)".into(),
+            "-12.5".into(),
+            "\tcmd".into(),
+            "a=b".into(),
+        ]];
+        let headers = ["a", "b", "c", "d", "e", "f"];
+        assert_eq!(
+            to_string(&headers, &rows, Delimiter::Csv),
+            "a,b,c,d,e,f\r\n\
+             \"'=HYPERLINK(\"\"http://evil\"\",\"\"x\"\")\",'+1+2,'@SUM(A1),-12.5,'\tcmd,a=b\r\n"
+        );
+        assert_eq!(
+            to_string(&headers, &rows, Delimiter::Tsv).lines().nth(1),
+            Some("'=HYPERLINK(\"http://evil\",\"x\")\t'+1+2\t'@SUM(A1)\t-12.5\t'\\tcmd\ta=b")
+        );
+    }
+
+    #[test]
+    fn test_tsv_escaping() {
+        assert_eq!(
+            to_string(&["id", "title"], &rows(), Delimiter::Tsv),
+            "id\ttitle\nt1\tplain\nt2\thas, comma and \"quotes\"\\nand a newline\nt3\t\n"
+        );
+    }
+}
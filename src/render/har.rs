@@ -0,0 +1,318 @@
+//! Export network logs as a HAR 1.2 archive
+//! (<http://www.softwareishard.com/blog/har-12-spec/>), which browser
+//! devtools and proxies can import.
+//!
+//! The SDK only records a request's total duration, so it is reported as
+//! the `wait` timing; phases it doesn't know about are `-1`.
+//!
+//! HAR files tend to be attached to bug reports, so header values are
+//! redacted the same way as for `--as-curl`.
+
+use serde::Serialize;
+use serde_json::{Map, Value};
+use url::Url;
+
+use crate::client::Redaction;
+use crate::models::logs::{NetworkLog, body_text, header};
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Har {
+    pub log: HarLog,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct HarLog {
+    pub version: &'static str,
+    pub creator: HarCreator,
+    pub entries: Vec<HarEntry>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct HarCreator {
+    pub name: &'static str,
+    pub version: &'static str,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HarEntry {
+    pub started_date_time: String,
+    pub time: f64,
+    pub request: HarRequest,
+    pub response: HarResponse,
+    pub cache: Map<String, Value>,
+    pub timings: HarTimings,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HarRequest {
+    pub method: String,
+    pub url: String,
+    pub http_version: &'static str,
+    pub cookies: Vec<HarPair>,
+    pub headers: Vec<HarPair>,
+    pub query_string: Vec<HarPair>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub post_data: Option<HarPostData>,
+    pub headers_size: i64,
+    pub body_size: i64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HarResponse {
+    pub status: u16,
+    pub status_text: String,
+    pub http_version: &'static str,
+    pub cookies: Vec<HarPair>,
+    pub headers: Vec<HarPair>,
+    pub content: HarContent,
+    #[serde(rename = "redirectURL")]
+    pub redirect_url: String,
+    pub headers_size: i64,
+    pub body_size: i64,
+}
+
+/// A header, cookie or query parameter.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct HarPair {
+    pub name: String,
+    pub value: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HarPostData {
+    pub mime_type: String,
+    pub text: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HarContent {
+    pub size: i64,
+    pub mime_type: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub text: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct HarTimings {
+    pub blocked: f64,
+    pub dns: f64,
+    pub connect: f64,
+    pub send: f64,
+    pub wait: f64,
+    pub receive: f64,
+    pub ssl: f64,
+}
+
+/// HTTP version to report; the SDK doesn't capture it.
+const HTTP_VERSION: &str = "HTTP/1.1";
+
+/// Used when an entry has no timestamp, since HAR requires one.
+const UNKNOWN_START: &str = "1970-01-01T00:00:00.000Z";
+
+/// Build a HAR archive with one entry per network log.
+pub fn to_har(logs: &[NetworkLog], redaction: &Redaction) -> Har {
+    Har {
+        log: HarLog {
+            version: "1.2",
+            creator: HarCreator {
+                name: "gleap",
+                version: env!("CARGO_PKG_VERSION"),
+            },
+            entries: logs.iter().map(|log| entry(log, redaction)).collect(),
+        },
+    }
+}
+
+fn entry(log: &NetworkLog, redaction: &Redaction) -> HarEntry {
+    let time = log.duration.unwrap_or(0.0).max(0.0);
+    HarEntry {
+        started_date_time: log
+            .time()
+            .map(|t| t.to_rfc3339_opts(chrono::SecondsFormat::Millis, true))
+            .unwrap_or_else(|| UNKNOWN_START.into()),
+        time,
+        request: request(log, redaction),
+        response: response(log, redaction),
+        cache: Map::new(),
+        timings: HarTimings {
+            blocked: -1.0,
+            dns: -1.0,
+            connect: -1.0,
+            send: 0.0,
+            wait: time,
+            receive: 0.0,
+            ssl: -1.0,
+        },
+    }
+}
+
+fn request(log: &NetworkLog, redaction: &Redaction) -> HarRequest {
+    let url = log.url.clone().unwrap_or_default();
+    let headers = log.request.as_ref().and_then(|r| r.headers.as_ref());
+    let body = log
+        .request
+        .as_ref()
+        .and_then(|r| r.body.as_ref())
+        .filter(|body| !body.is_null())
+        .map(body_text);
+
+    HarRequest {
+        method: log.method.clone().unwrap_or_else(|| "GET".into()),
+        query_string: query_string(&url),
+        url,
+        http_version: HTTP_VERSION,
+        cookies: Vec::new(),
+        headers: pairs(headers, redaction),
+        body_size: body.as_ref().map_or(0, |text| text.len() as i64),
+        post_data: body.map(|text| HarPostData {
+            mime_type: header(headers, "content-type").unwrap_or("").to_string(),
+            text,
+        }),
+        headers_size: -1,
+    }
+}
+
+fn response(log: &NetworkLog, redaction: &Redaction) -> HarResponse {
+    let response = log.response.as_ref();
+    let headers = response.and_then(|r| r.headers.as_ref());
+    let text = response
+        .and_then(|r| r.body.as_ref())
+        .filter(|body| !body.is_null())
+        .map(body_text);
+    let size = log.response_size().map_or(-1, |size| size as i64);
+
+    HarResponse {
+        // HAR uses 0 for requests that never got a response.
+        status: log.status().unwrap_or(0),
+        status_text: response
+            .and_then(|r| r.status_text.clone())
+            .unwrap_or_default(),
+        http_version: HTTP_VERSION,
+        cookies: Vec::new(),
+        headers: pairs(headers, redaction),
+        content: HarContent {
+            size,
+            mime_type: header(headers, "content-type").unwrap_or("").to_string(),
+            text,
+        },
+        redirect_url: header(headers, "location").unwrap_or("").to_string(),
+        headers_size: -1,
+        body_size: size,
+    }
+}
+
+fn pairs(headers: Option<&Map<String, Value>>, redaction: &Redaction) -> Vec<HarPair> {
+    headers
+        .into_iter()
+        .flatten()
+        .map(|(name, value)| HarPair {
+            name: name.clone(),
+            value: redaction.header_value(name, &body_text(value)),
+        })
+        .collect()
+}
+
+fn query_string(url: &str) -> Vec<HarPair> {
+    let Ok(url) = Url::parse(url) else {
+        return Vec::new();
+    };
+    url.query_pairs()
+        .map(|(name, value)| HarPair {
+            name: name.into_owned(),
+            value: value.into_owned(),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_har_entry() {
+        let logs: Vec<NetworkLog> = serde_json::from_value(json!([{
+            "type": "POST",
+            "url": "https://api.example.com/login?next=%2Fhome&x=1",
+            "date": "2026-03-01T10:00:01Z",
+            "duration": 1830,
+            "request": {
+                "headers": { "Content-Type": "application/json" },
+                "payload": { "user": "ann" }
+            },
+            "response": {
+                "status": 500,
+                "statusText": "Internal Server Error",
+                "headers": { "content-type": "application/json" },
+                "responseText": "{\"error\":\"boom\"}"
+            }
+        }]))
+        .unwrap();
+
+        let har = serde_json::to_value(to_har(&logs, &Redaction::default())).unwrap();
+        assert_eq!(har["log"]["version"], "1.2");
+        let entry = &har["log"]["entries"][0];
+        assert_eq!(entry["startedDateTime"], "2026-03-01T10:00:01.000Z");
+        assert_eq!(entry["time"], 1830.0);
+        assert_eq!(entry["timings"]["wait"], 1830.0);
+        assert_eq!(
+            entry["request"]["queryString"],
+            json!([{ "name": "next", "value": "/home" }, { "name": "x", "value": "1" }])
+        );
+        assert_eq!(
+            entry["request"]["postData"],
+            json!({ "mimeType": "application/json", "text": "{\"user\":\"ann\"}" })
+        );
+        assert_eq!(entry["response"]["status"], 500);
+        assert_eq!(entry["response"]["content"]["size"], 16);
+        assert_eq!(entry["response"]["content"]["mimeType"], "application/json");
+    }
+
+    #[test]
+    fn test_har_headers_are_redacted() {
+        let logs: Vec<NetworkLog> = serde_json::from_value(json!([{
+            "url": "https://api.example.com/me",
+            "request": {
+                "headers": { "Authorization": "Bearer abc123", "X-Api-Key": "k-1", "Accept": "*/*" }
+            },
+            "response": {
+                "status": 200,
+                "headers": { "Set-Cookie": "session=s3cret; Path=/; HttpOnly" }
+            }
+        }]))
+        .unwrap();
+
+        let har = serde_json::to_value(to_har(&logs, &Redaction::new(&["x-api-key"]))).unwrap();
+        let entry = &har["log"]["entries"][0];
+        assert_eq!(
+            entry["request"]["headers"],
+            json!([
+                { "name": "Accept", "value": "*/*" },
+                { "name": "Authorization", "value": "Bearer REDACTED" },
+                { "name": "X-Api-Key", "value": "REDACTED" }
+            ])
+        );
+        assert_eq!(
+            entry["response"]["headers"],
+            json!([{ "name": "Set-Cookie", "value": "session=REDACTED; Path=/; HttpOnly" }])
+        );
+    }
+
+    #[test]
+    fn test_har_entry_without_response() {
+        let logs: Vec<NetworkLog> =
+            serde_json::from_value(json!([{ "url": "https://example.com/" }])).unwrap();
+        let har = serde_json::to_value(to_har(&logs, &Redaction::default())).unwrap();
+        let entry = &har["log"]["entries"][0];
+        assert_eq!(entry["startedDateTime"], UNKNOWN_START);
+        assert_eq!(entry["request"]["method"], "GET");
+        assert_eq!(entry["response"]["status"], 0);
+        assert_eq!(entry["response"]["bodySize"], -1);
+        assert!(entry["request"].get("postData").is_none());
+    }
+}
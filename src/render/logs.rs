@@ -0,0 +1,158 @@
+use anstyle::{AnsiColor, Style};
+
+use crate::models::logs::{ConsoleLog, LogLevel};
+
+use super::terminal::escape_controls;
+
+/// Frames shown for a collapsed stack trace.
+const COLLAPSED_FRAMES: usize = 1;
+
+/// Options for [`console_view`].
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ConsoleOptions {
+    /// Color levels and dim timestamps with ANSI escapes.
+    pub color: bool,
+    /// Print every stack frame instead of the first one and a count.
+    pub full_stack: bool,
+}
+
+/// Render console entries one per line as `time LEVEL message`, with
+/// continuation lines and stack frames indented beneath. Control characters
+/// in the captured text are escaped.
+pub fn console_view(logs: &[ConsoleLog], options: ConsoleOptions) -> String {
+    let mut out = String::new();
+    for log in logs {
+        let time = log
+            .time()
+            .map(|t| t.format("%H:%M:%S%.3f").to_string())
+            .or_else(|| log.timestamp.as_deref().map(escape_controls))
+            .unwrap_or_else(|| "-".repeat(12));
+        let level = escape_controls(log.level.as_ref().map(LogLevel::as_str).unwrap_or("LOG"));
+        let message = escape_controls(log.message.as_deref().unwrap_or(""));
+        let mut lines = message.lines();
+
+        out.push_str(&paint(options.color, DIM, &time));
+        out.push(' ');
+        out.push_str(&paint(
+            options.color,
+            level_style(log.level.as_ref()),
+            &format!("{level:<7}"),
+        ));
+        out.push(' ');
+        out.push_str(lines.next().unwrap_or(""));
+        out.push('\n');
+        for line in lines {
+            out.push_str(&format!("{:21}{line}\n", ""));
+        }
+
+        if let Some(stack) = &log.stack {
+            let stack = escape_controls(stack);
+            let frames = stack_frames(&stack, &message);
+            let shown = if options.full_stack {
+                frames.len()
+            } else {
+                frames.len().min(COLLAPSED_FRAMES)
+            };
+            for frame in &frames[..shown] {
+                out.push_str(&format!("{:21}{}\n", "", paint(options.color, DIM, frame)));
+            }
+            if shown < frames.len() {
+                let more = format!("… {} more frames", frames.len() - shown);
+                out.push_str(&format!("{:21}{}\n", "", paint(options.color, DIM, &more)));
+            }
+        }
+    }
+    out
+}
+
+/// Stack lines without blanks and without a leading repeat of the message.
+fn stack_frames<'a>(stack: &'a str, message: &str) -> Vec<&'a str> {
+    let first_message_line = message.lines().next().unwrap_or("").trim();
+    let mut frames: Vec<&str> = stack
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .collect();
+    if frames.first() == Some(&first_message_line) {
+        frames.remove(0);
+    }
+    frames
+}
+
+const DIM: Style = Style::new().dimmed();
+
+fn level_style(level: Option<&LogLevel>) -> Style {
+    match level {
+        Some(LogLevel::Error) => AnsiColor::Red.on_default().bold(),
+        Some(LogLevel::Warning) => AnsiColor::Yellow.on_default(),
+        Some(LogLevel::Info) => AnsiColor::Blue.on_default(),
+        _ => DIM,
+    }
+}
+
+fn paint(color: bool, style: Style, text: &str) -> String {
+    if color {
+        format!("{style}{text}{style:#}")
+    } else {
+        text.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn logs() -> Vec<ConsoleLog> {
+        serde_json::from_value(json!([
+            { "log": "App booted", "priority": "INFO", "date": "2026-03-01T10:00:00.250Z" },
+            {
+                "log": "TypeError: x is undefined\nwhile logging in",
+                "priority": "ERROR",
+                "date": "2026-03-01T10:00:01Z",
+                "stack": "TypeError: x is undefined\n    at login (app.js:1:2)\n    at onClick (ui.js:3:4)\n    at dispatch (react.js:5:6)"
+            }
+        ]))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_collapsed_stack() {
+        assert_eq!(
+            console_view(&logs(), ConsoleOptions::default()),
+            "10:00:00.250 INFO    App booted\n\
+             10:00:01.000 ERROR   TypeError: x is undefined\n\
+             \x20                    while logging in\n\
+             \x20                    at login (app.js:1:2)\n\
+             \x20                    … 2 more frames\n"
+        );
+    }
+
+    #[test]
+    fn test_control_characters_are_escaped() {
+        let logs: Vec<ConsoleLog> = serde_json::from_value(json!([{
+            "log": "\u{1b}]52;c;aGk=\u{7}copied",
+            "priority": "INFO",
+            "date": "2026-03-01T10:00:00Z",
+            "stack": "at \u{1b}[8mhidden (app.js:1:2)"
+        }]))
+        .unwrap();
+        assert_eq!(
+            console_view(&logs, ConsoleOptions::default()),
+            "10:00:00.000 INFO    \\u{1b}]52;c;aGk=\\u{7}copied\n\
+             \x20                    at \\u{1b}[8mhidden (app.js:1:2)\n"
+        );
+    }
+
+    #[test]
+    fn test_full_stack_and_color() {
+        let options = ConsoleOptions {
+            color: true,
+            full_stack: true,
+        };
+        let view = console_view(&logs(), options);
+        assert!(view.contains("at dispatch (react.js:5:6)"));
+        assert!(!view.contains("more frames"));
+        assert!(view.contains("\x1b[1m\x1b[31mERROR  \x1b[0m"));
+    }
+}
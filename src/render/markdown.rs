@@ -0,0 +1,368 @@
+//! Conversion of Markdown into rich message documents.
+//!
+//! The inverse of [`richtext`](super::richtext): produces the
+//! `{"type": "doc", "content": [...]}` structure the messages endpoint
+//! accepts as a rich `comment`. Line breaks inside a paragraph are kept as
+//! hard breaks, since replies are written like chat messages rather than
+//! reflowed prose.
+
+use pulldown_cmark::{CodeBlockKind, Event, Options, Parser, Tag, TagEnd};
+use serde_json::{Map, Value, json};
+
+/// Convert Markdown into a rich document.
+pub fn to_doc(markdown: &str) -> Value {
+    let mut builder = Builder::new();
+    for event in Parser::new_ext(markdown, Options::ENABLE_STRIKETHROUGH) {
+        builder.event(event);
+    }
+    builder.finish()
+}
+
+/// Convert text into a message comment: a rich document when the Markdown
+/// carries formatting, otherwise the original text as a plain string.
+pub fn to_comment(text: &str) -> Value {
+    let doc = to_doc(text);
+    if is_plain(&doc) {
+        Value::String(text.to_string())
+    } else {
+        doc
+    }
+}
+
+/// True if the document holds nothing but paragraphs of unmarked text.
+fn is_plain(doc: &Value) -> bool {
+    nodes(doc).iter().all(|block| {
+        node_type(block) == Some("paragraph")
+            && nodes(block).iter().all(|inline| match node_type(inline) {
+                Some("text") => inline.get("marks").is_none(),
+                Some("hardBreak") => true,
+                _ => false,
+            })
+    })
+}
+
+fn node_type(node: &Value) -> Option<&str> {
+    node.get("type").and_then(Value::as_str)
+}
+
+fn nodes(node: &Value) -> &[Value] {
+    node.get("content")
+        .and_then(Value::as_array)
+        .map(Vec::as_slice)
+        .unwrap_or(&[])
+}
+
+struct Frame {
+    node: Map<String, Value>,
+    content: Vec<Value>,
+    /// Paragraph opened to hold inline content that appeared directly in a
+    /// block container (e.g. the text of a tight list item).
+    implicit: bool,
+}
+
+struct Image {
+    src: String,
+    title: String,
+    alt: String,
+}
+
+struct Builder {
+    stack: Vec<Frame>,
+    marks: Vec<Value>,
+    image: Option<Image>,
+}
+
+impl Builder {
+    fn new() -> Self {
+        Self {
+            stack: vec![Frame::new("doc", None)],
+            marks: Vec::new(),
+            image: None,
+        }
+    }
+
+    fn event(&mut self, event: Event<'_>) {
+        match event {
+            Event::Start(tag) => self.start(tag),
+            Event::End(tag) => self.end(tag),
+            Event::Text(text) => self.text(&text),
+            Event::Code(code) => {
+                self.marks.push(json!({ "type": "code" }));
+                self.text(&code);
+                self.marks.pop();
+            }
+            Event::Html(html) | Event::InlineHtml(html) => self.text(&html),
+            Event::SoftBreak | Event::HardBreak => {
+                if let Some(image) = &mut self.image {
+                    image.alt.push(' ');
+                } else {
+                    self.inline(json!({ "type": "hardBreak" }));
+                }
+            }
+            Event::Rule => self.leaf_block(json!({ "type": "horizontalRule" })),
+            _ => {}
+        }
+    }
+
+    fn start(&mut self, tag: Tag<'_>) {
+        match tag {
+            Tag::Paragraph => self.open("paragraph", None),
+            Tag::Heading { level, .. } => {
+                self.open("heading", Some(json!({ "level": level as u8 })))
+            }
+            Tag::BlockQuote(_) => self.open("blockquote", None),
+            Tag::CodeBlock(kind) => {
+                let language = match kind {
+                    CodeBlockKind::Fenced(info) => info
+                        .split_whitespace()
+                        .next()
+                        .map(|lang| Value::String(lang.to_string())),
+                    CodeBlockKind::Indented => None,
+                };
+                self.open(
+                    "codeBlock",
+                    Some(json!({ "language": language.unwrap_or(Value::Null) })),
+                )
+            }
+            Tag::List(Some(start)) => self.open("orderedList", Some(json!({ "start": start }))),
+            Tag::List(None) => self.open("bulletList", None),
+            Tag::Item => self.open("listItem", None),
+            Tag::Emphasis => self.marks.push(json!({ "type": "italic" })),
+            Tag::Strong => self.marks.push(json!({ "type": "bold" })),
+            Tag::Strikethrough => self.marks.push(json!({ "type": "strike" })),
+            Tag::Link { dest_url, .. } => self
+                .marks
+                .push(json!({ "type": "link", "attrs": { "href": dest_url.to_string() } })),
+            Tag::Image {
+                dest_url, title, ..
+            } => {
+                self.image = Some(Image {
+                    src: dest_url.to_string(),
+                    title: title.to_string(),
+                    alt: String::new(),
+                })
+            }
+            _ => {}
+        }
+    }
+
+    fn end(&mut self, tag: TagEnd) {
+        match tag {
+            TagEnd::Paragraph
+            | TagEnd::Heading(_)
+            | TagEnd::BlockQuote(_)
+            | TagEnd::CodeBlock
+            | TagEnd::List(_)
+            | TagEnd::Item => {
+                self.close_implicit();
+                self.close();
+            }
+            TagEnd::Emphasis | TagEnd::Strong | TagEnd::Strikethrough | TagEnd::Link => {
+                self.marks.pop();
+            }
+            TagEnd::Image => {
+                if let Some(image) = self.image.take() {
+                    let mut attrs = json!({ "src": image.src, "alt": image.alt });
+                    if !image.title.is_empty() {
+                        attrs["title"] = Value::String(image.title);
+                    }
+                    self.inline(json!({ "type": "image", "attrs": attrs }));
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn text(&mut self, text: &str) {
+        if let Some(image) = &mut self.image {
+            image.alt.push_str(text);
+            return;
+        }
+        if self.top_type() == Some("codeBlock") {
+            self.top()
+                .content
+                .push(json!({ "type": "text", "text": text }));
+            return;
+        }
+
+        let mut node = json!({ "type": "text", "text": text });
+        if !self.marks.is_empty() {
+            node["marks"] = Value::Array(self.marks.clone());
+        }
+        self.inline(node);
+    }
+
+    /// Add an inline node, opening a paragraph first if the current
+    /// container only accepts blocks.
+    fn inline(&mut self, node: Value) {
+        if !matches!(self.top_type(), Some("paragraph" | "heading")) {
+            self.stack.push(Frame {
+                implicit: true,
+                ..Frame::new("paragraph", None)
+            });
+        }
+        self.top().content.push(node);
+    }
+
+    fn leaf_block(&mut self, node: Value) {
+        self.close_implicit();
+        self.top().content.push(node);
+    }
+
+    fn open(&mut self, node_type: &str, attrs: Option<Value>) {
+        self.close_implicit();
+        self.stack.push(Frame::new(node_type, attrs));
+    }
+
+    fn close(&mut self) {
+        if self.stack.len() < 2 {
+            return;
+        }
+        let frame = self.stack.pop().expect("stack has a parent frame");
+        let node = frame.into_value();
+        self.top().content.push(node);
+    }
+
+    fn close_implicit(&mut self) {
+        if self.stack.last().is_some_and(|frame| frame.implicit) {
+            self.close();
+        }
+    }
+
+    fn top(&mut self) -> &mut Frame {
+        self.stack.last_mut().expect("doc frame is never popped")
+    }
+
+    fn top_type(&self) -> Option<&str> {
+        self.stack
+            .last()
+            .and_then(|frame| frame.node.get("type"))
+            .and_then(Value::as_str)
+    }
+
+    fn finish(mut self) -> Value {
+        while self.stack.len() > 1 {
+            self.close();
+        }
+        self.stack.pop().expect("doc frame").into_value()
+    }
+}
+
+impl Frame {
+    fn new(node_type: &str, attrs: Option<Value>) -> Self {
+        let mut node = Map::new();
+        node.insert("type".into(), Value::String(node_type.to_string()));
+        if let Some(attrs) = attrs {
+            node.insert("attrs".into(), attrs);
+        }
+        Self {
+            node,
+            content: Vec::new(),
+            implicit: false,
+        }
+    }
+
+    fn into_value(mut self) -> Value {
+        // Code block text arrives line by line; TipTap stores it as one node
+        // without the trailing newline.
+        if self.node.get("type").and_then(Value::as_str) == Some("codeBlock") {
+            let code: String = self
+                .content
+                .iter()
+                .filter_map(|n| n.get("text").and_then(Value::as_str))
+                .collect();
+            let code = code.trim_end_matches('\n');
+            self.content = if code.is_empty() {
+                Vec::new()
+            } else {
+                vec![json!({ "type": "text", "text": code })]
+            };
+        }
+        if !self.content.is_empty() || self.node.get("type") == Some(&json!("doc")) {
+            self.node
+                .insert("content".into(), Value::Array(self.content));
+        }
+        Value::Object(self.node)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::render::richtext;
+
+    #[test]
+    fn test_openapi_rich_comment_example() {
+        let doc = to_doc("We've deployed a fix. **Please update to version 2.3.2**");
+        assert_eq!(
+            doc,
+            json!({
+                "type": "doc",
+                "content": [{
+                    "type": "paragraph",
+                    "content": [
+                        { "type": "text", "text": "We've deployed a fix. " },
+                        {
+                            "type": "text",
+                            "marks": [{ "type": "bold" }],
+                            "text": "Please update to version 2.3.2"
+                        }
+                    ]
+                }]
+            })
+        );
+    }
+
+    #[test]
+    fn test_code_block_and_tight_list() {
+        let doc = to_doc("Steps:\n\n- open app\n- tap `login`\n\n```rust\nfn main() {}\n```\n");
+        assert_eq!(
+            doc["content"][1],
+            json!({
+                "type": "bulletList",
+                "content": [
+                    { "type": "listItem", "content": [{
+                        "type": "paragraph",
+                        "content": [{ "type": "text", "text": "open app" }]
+                    }]},
+                    { "type": "listItem", "content": [{
+                        "type": "paragraph",
+                        "content": [
+                            { "type": "text", "text": "tap " },
+                            { "type": "text", "text": "login", "marks": [{ "type": "code" }] }
+                        ]
+                    }]}
+                ]
+            })
+        );
+        assert_eq!(
+            doc["content"][2],
+            json!({
+                "type": "codeBlock",
+                "attrs": { "language": "rust" },
+                "content": [{ "type": "text", "text": "fn main() {}" }]
+            })
+        );
+    }
+
+    #[test]
+    fn test_round_trip_through_richtext() {
+        let markdown = "## Fix\n\n1. Update to **2.3.2**\n2. See [notes](https://example.com)\n\n> quoted\n\n---";
+        assert_eq!(richtext::to_markdown(&to_doc(markdown)), markdown);
+    }
+
+    #[test]
+    fn test_line_breaks_are_preserved() {
+        let doc = to_doc("first line\nsecond line");
+        assert_eq!(richtext::to_plain_text(&doc), "first line\nsecond line");
+    }
+
+    #[test]
+    fn test_to_comment_keeps_plain_text_as_string() {
+        assert_eq!(
+            to_comment("Thanks!\nWe are on it.\n\nCheers"),
+            json!("Thanks!\nWe are on it.\n\nCheers")
+        );
+        assert_eq!(to_comment("Use `npm ci`")["type"], json!("doc"));
+    }
+}
@@ -0,0 +1,11 @@
+pub mod columns;
+pub mod curl;
+pub mod delimited;
+pub mod har;
+pub mod logs;
+pub mod markdown;
+pub mod richtext;
+pub mod table;
+pub mod terminal;
+pub mod timeline;
+pub mod transcript;
@@ -0,0 +1,737 @@
+//! Conversion of rich message documents to Markdown, plain text and HTML.
+//!
+//! Message content (`MessageData.content`) is either a plain string or a
+//! TipTap/ProseMirror-style `{"type": "doc", "content": [...]}` tree. Unknown
+//! node types are rendered through their children, so new editor features
+//! degrade to readable text instead of disappearing.
+
+use serde_json::Value;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Flavor {
+    Markdown,
+    Plain,
+}
+
+/// Render message content as Markdown. Plain-string content is escaped, so
+/// it reads back as the literal text.
+pub fn to_markdown(content: &Value) -> String {
+    render(content, Flavor::Markdown)
+}
+
+/// Render message content as plain text, dropping all formatting.
+pub fn to_plain_text(content: &Value) -> String {
+    render(content, Flavor::Plain)
+}
+
+/// Render message content as an HTML fragment. Plain-string content is
+/// escaped and wrapped in a paragraph, keeping its line breaks.
+pub fn to_html(content: &Value) -> String {
+    match content {
+        Value::Null => String::new(),
+        Value::String(text) => text
+            .split("\n\n")
+            .filter(|para| !para.trim().is_empty())
+            .map(|para| format!("<p>{}</p>", escape_html(para).replace('\n', "<br>")))
+            .collect(),
+        Value::Object(_) if node_type(content) == Some("text") => {
+            html_inline(std::slice::from_ref(content))
+        }
+        Value::Object(_) => html_blocks(children(content)),
+        Value::Array(nodes) => html_blocks(nodes),
+        other => escape_html(&other.to_string()),
+    }
+}
+
+/// The URL if it's safe to make live: only `http`, `https` and `mailto`
+/// links are, since content is customer-authored and a `javascript:` or
+/// `data:` URL would run in whoever opens the rendering.
+fn safe_url(url: &str) -> Option<&str> {
+    let parsed = url::Url::parse(url).ok()?;
+    matches!(parsed.scheme(), "http" | "https" | "mailto").then_some(url)
+}
+
+/// Escape text for inclusion in HTML element content or attribute values.
+pub fn escape_html(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for ch in text.chars() {
+        match ch {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&#39;"),
+            ch => out.push(ch),
+        }
+    }
+    out
+}
+
+fn render(content: &Value, flavor: Flavor) -> String {
+    match content {
+        Value::Null => String::new(),
+        Value::String(text) => match flavor {
+            Flavor::Markdown => escape_markdown(text),
+            Flavor::Plain => text.clone(),
+        },
+        Value::Object(_) => {
+            if node_type(content) == Some("text") {
+                return render_inline(std::slice::from_ref(content), flavor);
+            }
+            render_blocks(children(content), flavor).join("\n\n")
+        }
+        Value::Array(nodes) => render_blocks(nodes, flavor).join("\n\n"),
+        other => other.to_string(),
+    }
+}
+
+fn node_type(node: &Value) -> Option<&str> {
+    node.get("type").and_then(Value::as_str)
+}
+
+fn children(node: &Value) -> &[Value] {
+    node.get("content")
+        .and_then(Value::as_array)
+        .map(Vec::as_slice)
+        .unwrap_or(&[])
+}
+
+fn attr<'a>(node: &'a Value, name: &str) -> Option<&'a Value> {
+    node.get("attrs").and_then(|attrs| attrs.get(name))
+}
+
+fn attr_str<'a>(node: &'a Value, name: &str) -> Option<&'a str> {
+    attr(node, name)
+        .and_then(Value::as_str)
+        .filter(|s| !s.is_empty())
+}
+
+fn is_inline(node: &Value) -> bool {
+    matches!(
+        node_type(node),
+        Some("text" | "hardBreak" | "mention" | "emoji" | "image")
+    )
+}
+
+/// Render a sequence of block nodes, dropping empty ones.
+fn render_blocks(nodes: &[Value], flavor: Flavor) -> Vec<String> {
+    let mut blocks = Vec::new();
+    let mut inline_run: Vec<Value> = Vec::new();
+
+    for node in nodes {
+        // Stray inline nodes at block level are grouped into a paragraph.
+        if is_inline(node) {
+            inline_run.push(node.clone());
+            continue;
+        }
+        if !inline_run.is_empty() {
+            blocks.push(render_inline(&inline_run, flavor));
+            inline_run.clear();
+        }
+        blocks.push(render_block(node, flavor));
+    }
+    if !inline_run.is_empty() {
+        blocks.push(render_inline(&inline_run, flavor));
+    }
+
+    blocks.retain(|block| !block.trim().is_empty());
+    blocks
+}
+
+fn render_block(node: &Value, flavor: Flavor) -> String {
+    match node_type(node) {
+        Some("paragraph") => render_inline(children(node), flavor),
+        Some("heading") => {
+            let text = render_inline(children(node), flavor);
+            match flavor {
+                Flavor::Markdown => {
+                    let level = attr(node, "level")
+                        .and_then(Value::as_u64)
+                        .unwrap_or(1)
+                        .clamp(1, 6) as usize;
+                    format!("{} {text}", "#".repeat(level))
+                }
+                Flavor::Plain => text,
+            }
+        }
+        Some("blockquote") => {
+            let inner = render_blocks(children(node), flavor).join("\n\n");
+            prefix_lines(&inner, "> ", "> ")
+        }
+        Some("codeBlock") => {
+            let code: String = children(node)
+                .iter()
+                .filter_map(|n| n.get("text").and_then(Value::as_str))
+                .collect();
+            match flavor {
+                Flavor::Markdown => {
+                    let language = attr_str(node, "language").unwrap_or("");
+                    let fence = code_fence(&code);
+                    format!(
+                        "{fence}{language}\n{}\n{fence}",
+                        code.trim_end_matches('\n')
+                    )
+                }
+                Flavor::Plain => code.trim_end_matches('\n').to_string(),
+            }
+        }
+        Some("bulletList") => render_list(node, None, flavor),
+        Some("orderedList") => {
+            let start = attr(node, "start").and_then(Value::as_u64).unwrap_or(1);
+            render_list(node, Some(start), flavor)
+        }
+        Some("horizontalRule") => match flavor {
+            Flavor::Markdown => "---".to_string(),
+            Flavor::Plain => "----------".to_string(),
+        },
+        // listItem outside a list, doc nested in doc, or anything unknown
+        _ => render_blocks(children(node), flavor).join("\n\n"),
+    }
+}
+
+fn render_list(node: &Value, start: Option<u64>, flavor: Flavor) -> String {
+    let mut lines = Vec::new();
+    for (i, item) in children(node).iter().enumerate() {
+        let marker = match start {
+            Some(start) => format!("{}. ", start + i as u64),
+            None => "- ".to_string(),
+        };
+        let indent = " ".repeat(marker.len());
+        // List items are tight: their blocks are separated by single newlines.
+        let body = render_blocks(children(item), flavor).join("\n");
+        lines.push(prefix_lines(&body, &marker, &indent));
+    }
+    lines.join("\n")
+}
+
+fn render_inline(nodes: &[Value], flavor: Flavor) -> String {
+    let mut out = String::new();
+    for node in nodes {
+        match node_type(node) {
+            Some("text") => {
+                let text = node.get("text").and_then(Value::as_str).unwrap_or("");
+                out.push_str(&apply_marks(text, node.get("marks"), flavor));
+            }
+            Some("hardBreak") => match flavor {
+                Flavor::Markdown => out.push_str("  \n"),
+                Flavor::Plain => out.push('\n'),
+            },
+            Some("mention") => {
+                let label = attr_str(node, "label")
+                    .or_else(|| attr_str(node, "id"))
+                    .unwrap_or("");
+                out.push('@');
+                out.push_str(label);
+            }
+            Some("emoji") => {
+                let name = attr_str(node, "name").unwrap_or("");
+                out.push_str(&format!(":{name}:"));
+            }
+            Some("image") => {
+                let src = attr_str(node, "src").unwrap_or("");
+                let alt = attr_str(node, "alt").or_else(|| attr_str(node, "title"));
+                match (flavor, safe_url(src)) {
+                    (Flavor::Markdown, Some(src)) => out.push_str(&format!(
+                        "![{}]({})",
+                        escape_markdown(alt.unwrap_or("")),
+                        link_destination(src)
+                    )),
+                    _ => out.push_str(&format!("[image: {}]", alt.unwrap_or(src))),
+                }
+            }
+            _ => out.push_str(&render_inline(children(node), flavor)),
+        }
+    }
+    out
+}
+
+fn apply_marks(text: &str, marks: Option<&Value>, flavor: Flavor) -> String {
+    let marks: Vec<&Value> = marks
+        .and_then(Value::as_array)
+        .map(|marks| marks.iter().collect())
+        .unwrap_or_default();
+    let has = |name: &str| marks.iter().any(|m| node_type(m) == Some(name));
+    let link = marks
+        .iter()
+        .find(|m| node_type(m) == Some("link"))
+        .and_then(|m| attr_str(m, "href"));
+
+    if flavor == Flavor::Plain {
+        return match link {
+            Some(href) if href != text => format!("{text} ({href})"),
+            _ => text.to_string(),
+        };
+    }
+
+    let mut out = if has("code") {
+        code_span(text)
+    } else {
+        escape_markdown(text)
+    };
+    if has("strike") {
+        out = wrap(&out, "~~");
+    }
+    if has("italic") {
+        out = wrap(&out, "_");
+    }
+    if has("bold") {
+        out = wrap(&out, "**");
+    }
+    if let Some(href) = link.and_then(safe_url) {
+        out = format!("[{out}]({})", link_destination(href));
+    }
+    out
+}
+
+/// Backslash-escape the characters that would otherwise start emphasis,
+/// code, links, strikethrough or raw HTML in customer-written text.
+pub fn escape_markdown(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for ch in text.chars() {
+        if matches!(ch, '\\' | '`' | '*' | '_' | '[' | ']' | '~' | '<' | '>') {
+            out.push('\\');
+        }
+        out.push(ch);
+    }
+    out
+}
+
+/// An inline code span whose fence is one backtick longer than the longest
+/// run of backticks inside it. Content starting or ending with a backtick
+/// is padded with spaces, which Markdown strips again.
+pub fn code_span(code: &str) -> String {
+    let longest = code.split(|c| c != '`').map(str::len).max().unwrap_or(0);
+    let fence = "`".repeat(longest + 1);
+    let pad = if code.starts_with('`') || code.ends_with('`') {
+        " "
+    } else {
+        ""
+    };
+    format!("{fence}{pad}{code}{pad}{fence}")
+}
+
+/// A URL as a Markdown link destination, with the characters that would end
+/// the destination early (`)`, spaces, line breaks) percent-encoded.
+fn link_destination(url: &str) -> String {
+    let mut out = String::with_capacity(url.len());
+    for ch in url.chars() {
+        if matches!(ch, ' ' | '(' | ')' | '<' | '>') || ch.is_control() {
+            let mut buf = [0; 4];
+            for byte in ch.encode_utf8(&mut buf).bytes() {
+                out.push_str(&format!("%{byte:02X}"));
+            }
+        } else {
+            out.push(ch);
+        }
+    }
+    out
+}
+
+/// Wrap text in a Markdown delimiter, keeping surrounding whitespace outside
+/// the delimiters (`** bold **` is not valid emphasis).
+fn wrap(text: &str, delimiter: &str) -> String {
+    let trimmed = text.trim();
+    if trimmed.is_empty() {
+        return text.to_string();
+    }
+    let start = text.len() - text.trim_start().len();
+    let end = start + trimmed.len();
+    format!(
+        "{}{delimiter}{trimmed}{delimiter}{}",
+        &text[..start],
+        &text[end..]
+    )
+}
+
+fn code_fence(code: &str) -> String {
+    let longest = code
+        .lines()
+        .map(|line| line.trim_start().chars().take_while(|c| *c == '`').count())
+        .max()
+        .unwrap_or(0);
+    "`".repeat(longest.max(2) + 1)
+}
+
+fn prefix_lines(text: &str, first: &str, rest: &str) -> String {
+    text.lines()
+        .enumerate()
+        .map(|(i, line)| {
+            let prefix = if i == 0 { first } else { rest };
+            if line.is_empty() {
+                prefix.trim_end().to_string()
+            } else {
+                format!("{prefix}{line}")
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn html_blocks(nodes: &[Value]) -> String {
+    let mut out = String::new();
+    let mut inline_run: Vec<Value> = Vec::new();
+    for node in nodes {
+        if is_inline(node) {
+            inline_run.push(node.clone());
+            continue;
+        }
+        if !inline_run.is_empty() {
+            out.push_str(&format!("<p>{}</p>", html_inline(&inline_run)));
+            inline_run.clear();
+        }
+        out.push_str(&html_block(node));
+    }
+    if !inline_run.is_empty() {
+        out.push_str(&format!("<p>{}</p>", html_inline(&inline_run)));
+    }
+    out
+}
+
+fn html_block(node: &Value) -> String {
+    match node_type(node) {
+        Some("paragraph") => {
+            let inner = html_inline(children(node));
+            if inner.is_empty() {
+                String::new()
+            } else {
+                format!("<p>{inner}</p>")
+            }
+        }
+        Some("heading") => {
+            let level = attr(node, "level")
+                .and_then(Value::as_u64)
+                .unwrap_or(1)
+                .clamp(1, 6);
+            format!("<h{level}>{}</h{level}>", html_inline(children(node)))
+        }
+        Some("blockquote") => format!("<blockquote>{}</blockquote>", html_blocks(children(node))),
+        Some("codeBlock") => {
+            let code: String = children(node)
+                .iter()
+                .filter_map(|n| n.get("text").and_then(Value::as_str))
+                .collect();
+            let class = attr_str(node, "language")
+                .map(|lang| format!(" class=\"language-{}\"", escape_html(lang)))
+                .unwrap_or_default();
+            format!("<pre><code{class}>{}</code></pre>", escape_html(&code))
+        }
+        Some("bulletList") => format!("<ul>{}</ul>", html_list_items(node)),
+        Some("orderedList") => match attr(node, "start").and_then(Value::as_u64) {
+            Some(start) if start != 1 => {
+                format!("<ol start=\"{start}\">{}</ol>", html_list_items(node))
+            }
+            _ => format!("<ol>{}</ol>", html_list_items(node)),
+        },
+        Some("horizontalRule") => "<hr>".to_string(),
+        _ => html_blocks(children(node)),
+    }
+}
+
+fn html_list_items(node: &Value) -> String {
+    children(node)
+        .iter()
+        .map(|item| format!("<li>{}</li>", html_blocks(children(item))))
+        .collect()
+}
+
+fn html_inline(nodes: &[Value]) -> String {
+    let mut out = String::new();
+    for node in nodes {
+        match node_type(node) {
+            Some("text") => {
+                let text = node.get("text").and_then(Value::as_str).unwrap_or("");
+                out.push_str(&html_marks(text, node.get("marks")));
+            }
+            Some("hardBreak") => out.push_str("<br>"),
+            Some("mention") => {
+                let label = attr_str(node, "label")
+                    .or_else(|| attr_str(node, "id"))
+                    .unwrap_or("");
+                out.push_str(&format!(
+                    "<span class=\"mention\">@{}</span>",
+                    escape_html(label)
+                ));
+            }
+            Some("emoji") => {
+                let name = attr_str(node, "name").unwrap_or("");
+                out.push_str(&format!(":{}:", escape_html(name)));
+            }
+            Some("image") => {
+                let alt = escape_html(attr_str(node, "alt").unwrap_or(""));
+                match attr_str(node, "src").and_then(safe_url) {
+                    Some(src) => {
+                        out.push_str(&format!("<img src=\"{}\" alt=\"{alt}\">", escape_html(src)))
+                    }
+                    None => out.push_str(&format!("<img alt=\"{alt}\">")),
+                }
+            }
+            _ => out.push_str(&html_inline(children(node))),
+        }
+    }
+    out
+}
+
+fn html_marks(text: &str, marks: Option<&Value>) -> String {
+    let mut out = escape_html(text);
+    let Some(marks) = marks.and_then(Value::as_array) else {
+        return out;
+    };
+    let has = |name: &str| marks.iter().any(|m| node_type(m) == Some(name));
+
+    if has("code") {
+        out = format!("<code>{out}</code>");
+    }
+    if has("strike") {
+        out = format!("<s>{out}</s>");
+    }
+    if has("underline") {
+        out = format!("<u>{out}</u>");
+    }
+    if has("italic") {
+        out = format!("<em>{out}</em>");
+    }
+    if has("bold") {
+        out = format!("<strong>{out}</strong>");
+    }
+    if let Some(href) = marks
+        .iter()
+        .find(|m| node_type(m) == Some("link"))
+        .and_then(|m| attr_str(m, "href"))
+        .and_then(safe_url)
+    {
+        out = format!("<a href=\"{}\">{out}</a>", escape_html(href));
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn text(value: &str) -> Value {
+        json!({ "type": "text", "text": value })
+    }
+
+    fn marked(value: &str, marks: Value) -> Value {
+        json!({ "type": "text", "text": value, "marks": marks })
+    }
+
+    fn paragraph(content: Vec<Value>) -> Value {
+        json!({ "type": "paragraph", "content": content })
+    }
+
+    fn doc(content: Vec<Value>) -> Value {
+        json!({ "type": "doc", "content": content })
+    }
+
+    #[test]
+    fn test_plain_string_passes_through() {
+        let content = json!("Thanks for reporting this.");
+        assert_eq!(to_markdown(&content), "Thanks for reporting this.");
+        assert_eq!(to_plain_text(&content), "Thanks for reporting this.");
+        assert_eq!(to_plain_text(&Value::Null), "");
+    }
+
+    #[test]
+    fn test_openapi_rich_comment_example() {
+        let content = doc(vec![paragraph(vec![
+            text("We've deployed a fix. "),
+            marked(
+                "Please update to version 2.3.2",
+                json!([{ "type": "bold" }]),
+            ),
+        ])]);
+
+        assert_eq!(
+            to_markdown(&content),
+            "We've deployed a fix. **Please update to version 2.3.2**"
+        );
+        assert_eq!(
+            to_plain_text(&content),
+            "We've deployed a fix. Please update to version 2.3.2"
+        );
+    }
+
+    #[test]
+    fn test_marks() {
+        let content = doc(vec![paragraph(vec![
+            marked("italic", json!([{ "type": "italic" }])),
+            text(" "),
+            marked("gone", json!([{ "type": "strike" }])),
+            text(" "),
+            marked("npm ci", json!([{ "type": "code" }])),
+            text(" "),
+            marked("both ", json!([{ "type": "bold" }, { "type": "italic" }])),
+            marked(
+                "docs",
+                json!([{ "type": "link", "attrs": { "href": "https://docs.gleap.io" } }]),
+            ),
+        ])]);
+
+        assert_eq!(
+            to_markdown(&content),
+            "_italic_ ~~gone~~ `npm ci` **_both_** [docs](https://docs.gleap.io)"
+        );
+        assert_eq!(
+            to_plain_text(&content),
+            "italic gone npm ci both docs (https://docs.gleap.io)"
+        );
+    }
+
+    #[test]
+    fn test_markdown_escaping() {
+        let content = doc(vec![paragraph(vec![
+            text("2*3 = 6, snake_case, [not a link] "),
+            marked("run `ls` or ``x``", json!([{ "type": "code" }])),
+            text(" "),
+            marked("`tick", json!([{ "type": "code" }])),
+        ])]);
+
+        assert_eq!(
+            to_markdown(&content),
+            "2\\*3 = 6, snake\\_case, \\[not a link\\] ``` run `ls` or ``x`` ``` `` `tick ``"
+        );
+        assert_eq!(
+            to_plain_text(&content),
+            "2*3 = 6, snake_case, [not a link] run `ls` or ``x`` `tick"
+        );
+    }
+
+    #[test]
+    fn test_markdown_html_is_escaped() {
+        let content = doc(vec![paragraph(vec![text("<img src=x onerror=alert(1)>")])]);
+        assert_eq!(to_markdown(&content), "\\<img src=x onerror=alert(1)\\>");
+        assert_eq!(to_markdown(&json!("<b>hi</b>")), "\\<b\\>hi\\</b\\>");
+    }
+
+    #[test]
+    fn test_link_destination_cannot_inject_links() {
+        let content = doc(vec![paragraph(vec![marked(
+            "docs",
+            json!([{ "type": "link", "attrs": { "href": "https://ok.example/a) [evil](https://evil" } }]),
+        )])]);
+        assert_eq!(
+            to_markdown(&content),
+            "[docs](https://ok.example/a%29%20[evil]%28https://evil)"
+        );
+    }
+
+    #[test]
+    fn test_blocks() {
+        let content = doc(vec![
+            json!({ "type": "heading", "attrs": { "level": 2 }, "content": [text("Steps")] }),
+            json!({ "type": "orderedList", "content": [
+                { "type": "listItem", "content": [paragraph(vec![text("Open the app")])] },
+                { "type": "listItem", "content": [
+                    paragraph(vec![text("Tap login")]),
+                    { "type": "bulletList", "content": [
+                        { "type": "listItem", "content": [paragraph(vec![text("nothing happens")])] }
+                    ]}
+                ]}
+            ]}),
+            json!({ "type": "blockquote", "content": [paragraph(vec![text("It worked yesterday")])] }),
+            json!({ "type": "codeBlock", "attrs": { "language": "js" }, "content": [text("login();\n")] }),
+            json!({ "type": "horizontalRule" }),
+            paragraph(vec![]),
+        ]);
+
+        assert_eq!(
+            to_markdown(&content),
+            "## Steps\n\n\
+             1. Open the app\n\
+             2. Tap login\n   - nothing happens\n\n\
+             > It worked yesterday\n\n\
+             ```js\nlogin();\n```\n\n\
+             ---"
+        );
+        assert_eq!(
+            to_plain_text(&content),
+            "Steps\n\n\
+             1. Open the app\n\
+             2. Tap login\n   - nothing happens\n\n\
+             > It worked yesterday\n\n\
+             login();\n\n\
+             ----------"
+        );
+    }
+
+    #[test]
+    fn test_inline_nodes() {
+        let content = doc(vec![paragraph(vec![
+            json!({ "type": "mention", "attrs": { "id": "u1", "label": "Ann" } }),
+            text(" see"),
+            json!({ "type": "hardBreak" }),
+            json!({ "type": "image", "attrs": { "src": "https://example.com/s.png", "alt": "screenshot" } }),
+        ])]);
+
+        assert_eq!(
+            to_markdown(&content),
+            "@Ann see  \n![screenshot](https://example.com/s.png)"
+        );
+        assert_eq!(to_plain_text(&content), "@Ann see\n[image: screenshot]");
+    }
+
+    #[test]
+    fn test_html() {
+        let content = doc(vec![
+            paragraph(vec![
+                text("Fixed in "),
+                marked("<v2>", json!([{ "type": "bold" }])),
+                text(" & "),
+                marked(
+                    "notes",
+                    json!([{ "type": "link", "attrs": { "href": "https://example.com?a=1&b=2" } }]),
+                ),
+            ]),
+            json!({ "type": "bulletList", "content": [
+                { "type": "listItem", "content": [paragraph(vec![text("one")])] }
+            ]}),
+        ]);
+
+        assert_eq!(
+            to_html(&content),
+            "<p>Fixed in <strong>&lt;v2&gt;</strong> &amp; \
+             <a href=\"https://example.com?a=1&amp;b=2\">notes</a></p>\
+             <ul><li><p>one</p></li></ul>"
+        );
+        assert_eq!(
+            to_html(&json!("line one\nline two")),
+            "<p>line one<br>line two</p>"
+        );
+    }
+
+    #[test]
+    fn test_unsafe_urls_are_not_live() {
+        let content = doc(vec![paragraph(vec![
+            marked(
+                "click",
+                json!([{ "type": "link", "attrs": { "href": " JavaScript:alert(1)" } }]),
+            ),
+            text(" "),
+            marked(
+                "mail",
+                json!([{ "type": "link", "attrs": { "href": "mailto:help@example.com" } }]),
+            ),
+            json!({ "type": "image", "attrs": { "src": "data:text/html,<b>", "alt": "x" } }),
+        ])]);
+
+        assert_eq!(
+            to_html(&content),
+            "<p>click <a href=\"mailto:help@example.com\">mail</a><img alt=\"x\"></p>"
+        );
+        assert_eq!(
+            to_markdown(&content),
+            "click [mail](mailto:help@example.com)[image: x]"
+        );
+    }
+
+    #[test]
+    fn test_unknown_nodes_render_children() {
+        let content = doc(vec![json!({
+            "type": "callout",
+            "content": [paragraph(vec![text("Heads up")])]
+        })]);
+        assert_eq!(to_plain_text(&content), "Heads up");
+    }
+}
@@ -0,0 +1,436 @@
+use chrono::{DateTime, Utc};
+use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
+
+use crate::models::logs::NetworkLog;
+use crate::models::message::Message;
+use crate::models::ticket::Ticket;
+
+use super::richtext;
+use super::terminal::escape_controls;
+
+const COLUMN_GAP: &str = "  ";
+const ELLIPSIS: char = '…';
+
+/// Minimum width a flexible column is shrunk to before the table is allowed
+/// to overflow the terminal.
+const MIN_FLEX_WIDTH: usize = 12;
+
+/// A plain-text table with left-aligned columns.
+///
+/// One column may be marked flexible: when the rendered table is wider than
+/// the available width, that column is truncated (with an ellipsis) to fit.
+#[derive(Debug, Clone)]
+pub struct Table {
+    headers: Vec<String>,
+    rows: Vec<Vec<String>>,
+    flex: Option<usize>,
+}
+
+impl Table {
+    pub fn new(headers: &[&str]) -> Self {
+        Self {
+            headers: headers.iter().map(|h| h.to_string()).collect(),
+            rows: Vec::new(),
+            flex: None,
+        }
+    }
+
+    /// Mark the column at `index` as the one to truncate when space runs out.
+    pub fn flexible(mut self, index: usize) -> Self {
+        self.flex = Some(index);
+        self
+    }
+
+    /// Append a row. Missing trailing cells render as empty; embedded
+    /// newlines and tabs are collapsed so each row stays on one line.
+    pub fn push_row(&mut self, row: Vec<String>) {
+        self.rows
+            .push(row.iter().map(|cell| single_line(cell)).collect());
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.rows.is_empty()
+    }
+
+    /// Render the table, fitting it into `max_width` columns when given.
+    pub fn render(&self, max_width: Option<usize>) -> String {
+        let mut widths: Vec<usize> = self.headers.iter().map(|h| h.width()).collect();
+        for row in &self.rows {
+            for (i, cell) in row.iter().enumerate().take(widths.len()) {
+                widths[i] = widths[i].max(cell.width());
+            }
+        }
+
+        if let (Some(max_width), Some(flex)) = (max_width, self.flex)
+            && flex < widths.len()
+        {
+            let total: usize = widths.iter().sum::<usize>() + COLUMN_GAP.len() * (widths.len() - 1);
+            if total > max_width {
+                let excess = total - max_width;
+                widths[flex] = widths[flex]
+                    .saturating_sub(excess)
+                    .max(MIN_FLEX_WIDTH.min(widths[flex]));
+            }
+        }
+
+        let mut out = String::new();
+        self.render_row(&mut out, &self.headers, &widths);
+        for row in &self.rows {
+            self.render_row(&mut out, row, &widths);
+        }
+        out
+    }
+
+    fn render_row(&self, out: &mut String, row: &[String], widths: &[usize]) {
+        let last = widths.len() - 1;
+        let mut line = String::new();
+        for (i, width) in widths.iter().enumerate() {
+            let cell = row.get(i).map(String::as_str).unwrap_or("");
+            let cell = truncate(cell, *width);
+            line.push_str(&cell);
+            if i < last {
+                line.push_str(&" ".repeat(width - cell.width()));
+                line.push_str(COLUMN_GAP);
+            }
+        }
+        out.push_str(line.trim_end());
+        out.push('\n');
+    }
+}
+
+/// Build the summary table used for ticket lists.
+pub fn ticket_table(tickets: &[Ticket], now: DateTime<Utc>) -> Table {
+    let mut table = Table::new(&[
+        "ID", "STATUS", "PRIORITY", "TYPE", "TITLE", "AGE", "ASSIGNEE",
+    ])
+    .flexible(4);
+
+    for ticket in tickets {
+        table.push_row(vec![
+            ticket.id.clone(),
+            ticket
+                .status
+                .as_ref()
+                .map(|s| s.as_str().to_string())
+                .unwrap_or_default(),
+            ticket
+                .priority
+                .as_ref()
+                .map(|p| p.as_str().to_string())
+                .unwrap_or_default(),
+            ticket
+                .ticket_type
+                .as_ref()
+                .map(|t| t.as_str().to_string())
+                .unwrap_or_default(),
+            ticket.title.clone().unwrap_or_default(),
+            ticket
+                .created_at
+                .as_deref()
+                .and_then(|ts| age(ts, now))
+                .unwrap_or_default(),
+            ticket
+                .processing_user
+                .as_ref()
+                .and_then(|u| u.display_name())
+                .unwrap_or_default(),
+        ]);
+    }
+
+    table
+}
+
+/// Build the summary table used for message lists.
+pub fn message_table(messages: &[Message], now: DateTime<Utc>) -> Table {
+    let mut table = Table::new(&["ID", "TYPE", "AUTHOR", "AGE", "TEXT"]).flexible(4);
+
+    for message in messages {
+        table.push_row(vec![
+            message.id.clone(),
+            message
+                .message_type
+                .as_ref()
+                .map(|t| t.as_str().to_string())
+                .unwrap_or_default(),
+            message_author(message),
+            message
+                .created_at
+                .as_deref()
+                .and_then(|ts| age(ts, now))
+                .unwrap_or_default(),
+            message_text(message),
+        ]);
+    }
+
+    table
+}
+
+/// Build the summary table used for network logs. Requests without a
+/// response show `-` as their status.
+pub fn network_table(logs: &[NetworkLog]) -> Table {
+    let mut table = Table::new(&["#", "METHOD", "STATUS", "TIME", "SIZE", "URL"]).flexible(5);
+
+    for (index, log) in logs.iter().enumerate() {
+        table.push_row(vec![
+            index.to_string(),
+            log.method.clone().unwrap_or_default(),
+            log.status()
+                .map(|status| status.to_string())
+                .unwrap_or_else(|| "-".into()),
+            log.duration.map(duration).unwrap_or_default(),
+            log.response_size().map(size).unwrap_or_default(),
+            log.url.clone().unwrap_or_default(),
+        ]);
+    }
+
+    table
+}
+
+/// Format milliseconds as `120 ms` or, from a second up, `1.83 s`.
+pub fn duration(ms: f64) -> String {
+    if ms < 1000.0 {
+        format!("{} ms", ms.round())
+    } else {
+        format!("{:.2} s", ms / 1000.0)
+    }
+}
+
+/// Format a byte count as `512 B`, `1.2 KB` or `3.4 MB`.
+pub fn size(bytes: u64) -> String {
+    match bytes {
+        b if b < 1024 => format!("{b} B"),
+        b if b < 1024 * 1024 => format!("{:.1} KB", b as f64 / 1024.0),
+        b => format!("{:.1} MB", b as f64 / (1024.0 * 1024.0)),
+    }
+}
+
+/// Render a single ticket as aligned `Field  value` lines, with the
+/// description (if any) following after a blank line.
+pub fn ticket_detail(ticket: &Ticket, now: DateTime<Utc>) -> String {
+    let created = ticket.created_at.as_deref().map(|ts| match age(ts, now) {
+        Some(age) => format!("{ts} ({age} ago)"),
+        None => ts.to_string(),
+    });
+
+    let fields = [
+        ("ID", Some(ticket.id.clone())),
+        ("Title", ticket.title.clone()),
+        ("Status", ticket.status.as_ref().map(|s| s.as_str().into())),
+        (
+            "Priority",
+            ticket.priority.as_ref().map(|p| p.as_str().into()),
+        ),
+        (
+            "Type",
+            ticket.ticket_type.as_ref().map(|t| t.as_str().into()),
+        ),
+        (
+            "Assignee",
+            ticket
+                .processing_user
+                .as_ref()
+                .and_then(|u| u.display_name()),
+        ),
+        (
+            "Reporter",
+            ticket.session.as_ref().and_then(|s| s.display_name()),
+        ),
+        (
+            "Tags",
+            ticket
+                .tags
+                .as_ref()
+                .filter(|tags| !tags.is_empty())
+                .map(|tags| tags.join(", ")),
+        ),
+        ("Created", created),
+        ("Updated", ticket.updated_at.clone()),
+    ];
+
+    let label_width = fields
+        .iter()
+        .map(|(label, _)| label.len())
+        .max()
+        .unwrap_or(0);
+    let mut out = String::new();
+    for (label, value) in fields {
+        if let Some(value) = value {
+            out.push_str(&format!("{label:<label_width$}  {}\n", single_line(&value)));
+        }
+    }
+
+    if let Some(description) = ticket
+        .description
+        .as_deref()
+        .filter(|d| !d.trim().is_empty())
+    {
+        out.push('\n');
+        out.push_str(&escape_controls(description.trim_end()));
+        out.push('\n');
+    }
+
+    out
+}
+
+/// Compact relative age of an RFC 3339 timestamp (e.g. `5m`, `3h`, `12d`).
+pub fn age(timestamp: &str, now: DateTime<Utc>) -> Option<String> {
+    let then = DateTime::parse_from_rfc3339(timestamp).ok()?;
+    let secs = (now - then.with_timezone(&Utc)).num_seconds().max(0);
+
+    let age = match secs {
+        s if s < 60 => "now".to_string(),
+        s if s < 3_600 => format!("{}m", s / 60),
+        s if s < 86_400 => format!("{}h", s / 3_600),
+        s if s < 30 * 86_400 => format!("{}d", s / 86_400),
+        s if s < 365 * 86_400 => format!("{}mo", s / (30 * 86_400)),
+        s => format!("{}y", s / (365 * 86_400)),
+    };
+    Some(age)
+}
+
+fn message_author(message: &Message) -> String {
+    if let Some(name) = message.user.as_ref().and_then(|u| u.display_name()) {
+        return name;
+    }
+    if let Some(name) = message.session.as_ref().and_then(|s| s.display_name()) {
+        return name;
+    }
+    if message.bot == Some(true) {
+        return "bot".to_string();
+    }
+    String::new()
+}
+
+fn message_text(message: &Message) -> String {
+    message
+        .data
+        .as_ref()
+        .and_then(|d| d.content.as_ref())
+        .map(richtext::to_plain_text)
+        .unwrap_or_default()
+}
+
+/// Collapse whitespace (line breaks included) to single spaces and escape
+/// any other control characters.
+fn single_line(text: &str) -> String {
+    escape_controls(&text.split_whitespace().collect::<Vec<_>>().join(" "))
+}
+
+fn truncate(text: &str, width: usize) -> String {
+    if text.width() <= width {
+        return text.to_string();
+    }
+
+    let mut out = String::new();
+    let mut used = 0;
+    for ch in text.chars() {
+        let ch_width = ch.width().unwrap_or(0);
+        if used + ch_width + 1 > width {
+            break;
+        }
+        out.push(ch);
+        used += ch_width;
+    }
+    out.push(ELLIPSIS);
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn now() -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339("2026-02-12T12:00:00Z")
+            .unwrap()
+            .with_timezone(&Utc)
+    }
+
+    #[test]
+    fn test_render_aligns_columns() {
+        let mut table = Table::new(&["ID", "TITLE"]);
+        table.push_row(vec!["1".into(), "short".into()]);
+        table.push_row(vec!["12345".into(), "longer title".into()]);
+
+        assert_eq!(
+            table.render(None),
+            "ID     TITLE\n1      short\n12345  longer title\n"
+        );
+    }
+
+    #[test]
+    fn test_render_truncates_flexible_column() {
+        let mut table = Table::new(&["ID", "TITLE", "AGE"]).flexible(1);
+        table.push_row(vec![
+            "1".into(),
+            "a very long ticket title that will not fit".into(),
+            "3d".into(),
+        ]);
+
+        let rendered = table.render(Some(24));
+        for line in rendered.lines() {
+            assert!(line.width() <= 24, "line too wide: {line:?}");
+        }
+        assert!(rendered.contains('…'));
+    }
+
+    #[test]
+    fn test_push_row_collapses_newlines() {
+        let mut table = Table::new(&["TEXT"]);
+        table.push_row(vec!["line one\nline\ttwo".into()]);
+        assert_eq!(table.render(None), "TEXT\nline one line two\n");
+    }
+
+    #[test]
+    fn test_ticket_detail_escapes_control_characters() {
+        let ticket: Ticket = serde_json::from_value(serde_json::json!({
+            "id": "t1",
+            "title": "Login\u{1b}[2J broken",
+            "description": "Steps:\n\u{1b}]52;c;aGk=\u{7}click"
+        }))
+        .unwrap();
+        assert_eq!(
+            ticket_detail(&ticket, now()),
+            "ID        t1\nTitle     Login\\u{1b}[2J broken\n\nSteps:\n\\u{1b}]52;c;aGk=\\u{7}click\n"
+        );
+    }
+
+    #[test]
+    fn test_network_table() {
+        let logs: Vec<NetworkLog> = serde_json::from_value(serde_json::json!([
+            {
+                "type": "POST",
+                "url": "https://api.example.com/login",
+                "duration": 1830,
+                "response": { "status": 500, "responseText": "{\"error\":\"boom\"}" }
+            },
+            {
+                "type": "GET",
+                "url": "https://cdn.example.com/logo.png",
+                "duration": 12.4,
+                "response": { "status": 200, "headers": { "Content-Length": "2048" } }
+            },
+            { "type": "GET", "url": "https://api.example.com/pending" }
+        ]))
+        .unwrap();
+
+        assert_eq!(
+            network_table(&logs).render(None),
+            "#  METHOD  STATUS  TIME    SIZE    URL\n\
+             0  POST    500     1.83 s  16 B    https://api.example.com/login\n\
+             1  GET     200     12 ms   2.0 KB  https://cdn.example.com/logo.png\n\
+             2  GET     -                       https://api.example.com/pending\n"
+        );
+    }
+
+    #[test]
+    fn test_age() {
+        assert_eq!(age("2026-02-12T11:59:30Z", now()).as_deref(), Some("now"));
+        assert_eq!(age("2026-02-12T11:15:00Z", now()).as_deref(), Some("45m"));
+        assert_eq!(age("2026-02-12T02:00:00Z", now()).as_deref(), Some("10h"));
+        assert_eq!(
+            age("2026-02-01T12:00:00.000Z", now()).as_deref(),
+            Some("11d")
+        );
+        assert_eq!(age("not a date", now()), None);
+    }
+}
@@ -0,0 +1,38 @@
+//! Making captured text safe to print to a terminal.
+
+/// Replace control characters other than newline and tab with a visible
+/// `\u{..}` escape. Console logs, URLs and ticket text are written by
+/// customers, and a raw ESC or C1 control in them could otherwise rewrite
+/// the terminal (move the cursor, hide text, set the clipboard).
+pub fn escape_controls(text: &str) -> String {
+    if !text.chars().any(is_unsafe) {
+        return text.to_string();
+    }
+    let mut out = String::with_capacity(text.len());
+    for ch in text.chars() {
+        if is_unsafe(ch) {
+            out.extend(ch.escape_unicode());
+        } else {
+            out.push(ch);
+        }
+    }
+    out
+}
+
+fn is_unsafe(ch: char) -> bool {
+    ch.is_control() && ch != '\n' && ch != '\t'
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_escape_controls() {
+        assert_eq!(escape_controls("plain\n\ttext"), "plain\n\ttext");
+        assert_eq!(
+            escape_controls("a\x1b]52;c;aGk=\x07b\rc\u{9b}"),
+            "a\\u{1b}]52;c;aGk=\\u{7}b\\u{d}c\\u{9b}"
+        );
+    }
+}
@@ -0,0 +1,245 @@
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::models::logs::ActivityLog;
+use crate::models::message::Message;
+use crate::models::metrics::{self, TicketMetrics};
+use crate::models::ticket::{Ticket, UserRef};
+
+use super::table::Table;
+
+/// One change from the activity log, described for people.
+#[derive(Debug, Clone, Serialize)]
+pub struct Event {
+    #[serde(rename = "createdAt")]
+    pub created_at: Option<String>,
+    /// Team member who made the change, if the log names one.
+    pub actor: Option<String>,
+    /// What changed, e.g. `status OPEN → DONE` or `assigned to Ann Lee`.
+    pub change: String,
+}
+
+/// A ticket's activity in chronological order, with its lifecycle metrics.
+#[derive(Debug, Clone, Serialize)]
+pub struct Timeline {
+    #[serde(rename = "ticketId")]
+    pub ticket_id: String,
+    pub events: Vec<Event>,
+    pub metrics: TicketMetrics,
+}
+
+impl Timeline {
+    /// Build a timeline from a ticket's activity log and messages (used for
+    /// the first response time). Open intervals run until `now`.
+    pub fn new(
+        ticket: &Ticket,
+        activity: &[ActivityLog],
+        messages: &[Message],
+        now: DateTime<Utc>,
+    ) -> Self {
+        let mut logs: Vec<&ActivityLog> = activity.iter().collect();
+        // Same order as the metrics: by time, whatever the offset or precision
+        // of the timestamps, then by the raw value for any that don't parse.
+        logs.sort_by(|a, b| (a.time(), &a.created_at).cmp(&(b.time(), &b.created_at)));
+
+        let events = logs
+            .into_iter()
+            .map(|log| Event {
+                created_at: log.created_at.clone(),
+                actor: log.user.as_ref().and_then(UserRef::display_name),
+                change: describe(log),
+            })
+            .collect();
+
+        Self {
+            ticket_id: ticket.id.clone(),
+            events,
+            metrics: metrics::ticket_metrics(ticket, activity, messages, now),
+        }
+    }
+
+    /// The events as a table followed by the metrics, fitted into
+    /// `max_width` columns when given.
+    pub fn to_text(&self, max_width: Option<usize>) -> String {
+        let mut table = Table::new(&["TIME", "BY", "CHANGE"]).flexible(2);
+        for event in &self.events {
+            table.push_row(vec![
+                event
+                    .created_at
+                    .as_deref()
+                    .map(timestamp)
+                    .unwrap_or_default(),
+                event.actor.clone().unwrap_or_else(|| "System".into()),
+                event.change.clone(),
+            ]);
+        }
+
+        let metrics = &self.metrics;
+        let elapsed = |secs: Option<i64>, at: &Option<String>| {
+            Some(format!("{} ({})", span(secs?), timestamp(at.as_deref()?)))
+        };
+        let time_in_status = metrics
+            .time_in_status
+            .iter()
+            .map(|t| format!("{} {}", t.status, span(t.secs)))
+            .collect::<Vec<_>>()
+            .join(", ");
+        let fields = [
+            (
+                "First response",
+                elapsed(
+                    metrics.time_to_first_response_secs,
+                    &metrics.first_response_at,
+                ),
+            ),
+            (
+                "Resolved",
+                elapsed(metrics.time_to_resolution_secs, &metrics.resolved_at),
+            ),
+            (
+                "Reopened",
+                match metrics.reopen_count {
+                    0 => None,
+                    1 => Some("1 time".into()),
+                    n => Some(format!("{n} times")),
+                },
+            ),
+            (
+                "Time in status",
+                Some(time_in_status).filter(|s| !s.is_empty()),
+            ),
+        ];
+
+        let mut out = if table.is_empty() {
+            "No activity recorded.\n".to_string()
+        } else {
+            table.render(max_width)
+        };
+        let label_width = fields
+            .iter()
+            .map(|(label, _)| label.len())
+            .max()
+            .unwrap_or(0);
+        let mut first = true;
+        for (label, value) in fields {
+            if let Some(value) = value {
+                if std::mem::take(&mut first) {
+                    out.push('\n');
+                }
+                out.push_str(&format!("{label:<label_width$}  {value}\n"));
+            }
+        }
+        out
+    }
+}
+
+/// Describe an activity entry, e.g. `status OPEN → INPROGRESS`.
+pub fn describe(log: &ActivityLog) -> String {
+    let action = log.action.as_deref().unwrap_or("");
+    let assignment = action == "ASSIGNED" || log.field.as_deref() == Some("processingUser");
+    if action == "CREATED" {
+        return "created".into();
+    }
+    if assignment {
+        return match log.new_value.as_ref().filter(|v| !v.is_null()) {
+            Some(user) => format!("assigned to {}", value_text(user)),
+            None => "unassigned".into(),
+        };
+    }
+
+    let subject = match &log.field {
+        Some(field) => field.clone(),
+        None => action.to_lowercase().replace('_', " "),
+    };
+    match (&log.old_value, &log.new_value) {
+        (Some(old), Some(new)) if !old.is_null() => {
+            format!("{subject} {} → {}", value_text(old), value_text(new))
+        }
+        (_, Some(new)) => format!("{subject} → {}", value_text(new)),
+        _ => subject,
+    }
+}
+
+/// A value from the activity log: strings as-is, users by name, anything
+/// else as compact JSON.
+fn value_text(value: &Value) -> String {
+    match value {
+        Value::String(text) => text.clone(),
+        Value::Null => "none".into(),
+        Value::Object(_) => serde_json::from_value::<UserRef>(value.clone())
+            .ok()
+            .and_then(|user| user.display_name())
+            .unwrap_or_else(|| value.to_string()),
+        other => other.to_string(),
+    }
+}
+
+/// Format seconds as the two largest units, e.g. `2d 3h`, `4h 5m`, `45s`.
+pub fn span(secs: i64) -> String {
+    let (days, hours, minutes) = (secs / 86_400, secs % 86_400 / 3_600, secs % 3_600 / 60);
+    let pair = |big: i64, big_unit: &str, small: i64, small_unit: &str| {
+        if small == 0 {
+            format!("{big}{big_unit}")
+        } else {
+            format!("{big}{big_unit} {small}{small_unit}")
+        }
+    };
+    match secs {
+        s if s < 60 => format!("{s}s"),
+        s if s < 3_600 => format!("{minutes}m"),
+        s if s < 86_400 => pair(hours, "h", minutes, "m"),
+        _ => pair(days, "d", hours, "h"),
+    }
+}
+
+fn timestamp(ts: &str) -> String {
+    DateTime::parse_from_rfc3339(ts)
+        .map(|dt| dt.to_utc().format("%Y-%m-%d %H:%M").to_string())
+        .unwrap_or_else(|_| ts.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_timeline_text() {
+        let ticket: Ticket = serde_json::from_value(json!({
+            "id": "t1",
+            "status": "DONE",
+            "createdAt": "2026-03-01T10:00:00Z"
+        }))
+        .unwrap();
+        let activity: Vec<ActivityLog> = serde_json::from_value(json!([
+            { "type": "STATUS_CHANGED", "field": "status", "oldValue": "OPEN", "newValue": "DONE", "user": { "firstName": "Ann" }, "createdAt": "2026-03-02T12:30:00Z" },
+            { "type": "ASSIGNED", "field": "processingUser", "newValue": { "email": "ann@example.com" }, "user": { "firstName": "Ben" }, "createdAt": "2026-03-01T09:05:00-01:00" },
+            { "type": "CREATED", "createdAt": "2026-03-01T10:00:00Z" }
+        ]))
+        .unwrap();
+        let now = DateTime::parse_from_rfc3339("2026-03-10T00:00:00Z")
+            .unwrap()
+            .to_utc();
+
+        assert_eq!(
+            Timeline::new(&ticket, &activity, &[], now).to_text(None),
+            "TIME              BY      CHANGE\n\
+             2026-03-01 10:00  System  created\n\
+             2026-03-01 10:05  Ben     assigned to ann@example.com\n\
+             2026-03-02 12:30  Ann     status OPEN → DONE\n\
+             \n\
+             Resolved        1d 2h (2026-03-02 12:30)\n\
+             Time in status  OPEN 1d 2h\n"
+        );
+    }
+
+    #[test]
+    fn test_span() {
+        assert_eq!(span(45), "45s");
+        assert_eq!(span(5 * 60 + 10), "5m");
+        assert_eq!(span(2 * 3_600), "2h");
+        assert_eq!(span(2 * 3_600 + 5 * 60), "2h 5m");
+        assert_eq!(span(3 * 86_400 + 4 * 3_600 + 59), "3d 4h");
+    }
+}
@@ -0,0 +1,334 @@
+use chrono::DateTime;
+use serde::Serialize;
+
+use crate::models::message::{Message, MessageType};
+use crate::models::ticket::Ticket;
+
+use super::richtext;
+
+/// Who (or what) produced a transcript entry.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EntryKind {
+    /// Message from the customer.
+    Customer,
+    /// Reply from a team member, visible to the customer.
+    Reply,
+    /// Internal note, visible to the team only.
+    Note,
+    /// Automated bot message.
+    Bot,
+    /// System event such as a status change (`FEEDBACK_UPDATED`).
+    Event,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Entry {
+    pub id: String,
+    pub kind: EntryKind,
+    pub author: String,
+    #[serde(rename = "createdAt")]
+    pub created_at: Option<String>,
+    /// Raw message content: a plain string or a rich document.
+    pub content: serde_json::Value,
+}
+
+/// A ticket's conversation in chronological order.
+#[derive(Debug, Clone, Serialize)]
+pub struct Transcript {
+    #[serde(rename = "ticketId")]
+    pub ticket_id: String,
+    pub title: Option<String>,
+    pub entries: Vec<Entry>,
+}
+
+impl Transcript {
+    /// Build a transcript from a ticket and its messages. Internal notes are
+    /// dropped unless `include_notes` is set.
+    pub fn new(ticket: &Ticket, messages: &[Message], include_notes: bool) -> Self {
+        let mut messages: Vec<&Message> = messages.iter().collect();
+        messages.sort_by(|a, b| {
+            a.index
+                .cmp(&b.index)
+                .then_with(|| a.created_at.cmp(&b.created_at))
+        });
+
+        let entries = messages
+            .into_iter()
+            .map(entry)
+            .filter(|entry| include_notes || entry.kind != EntryKind::Note)
+            .collect();
+
+        Self {
+            ticket_id: ticket.id.clone(),
+            title: ticket.title.clone(),
+            entries,
+        }
+    }
+
+    pub fn to_markdown(&self) -> String {
+        let mut out = format!(
+            "# {}\n\nTicket {}\n",
+            richtext::escape_markdown(&self.heading()),
+            richtext::code_span(&self.ticket_id)
+        );
+        for entry in &self.entries {
+            let when = entry.created_at.as_deref().map(timestamp);
+            out.push('\n');
+            if entry.kind == EntryKind::Event {
+                let text = richtext::escape_markdown(&richtext::to_plain_text(&entry.content));
+                match when {
+                    Some(when) => out.push_str(&format!("_{when} · {text}_\n")),
+                    None => out.push_str(&format!("_{text}_\n")),
+                }
+                continue;
+            }
+
+            out.push_str(&format!("### {}", richtext::escape_markdown(&entry.author)));
+            if let Some(label) = label(entry.kind) {
+                out.push_str(&format!(" ({label})"));
+            }
+            if let Some(when) = when {
+                out.push_str(&format!(" · {when}"));
+            }
+            out.push_str("\n\n");
+            out.push_str(richtext::to_markdown(&entry.content).trim_end());
+            out.push('\n');
+        }
+        out
+    }
+
+    pub fn to_text(&self) -> String {
+        let heading = format!("{} ({})", self.heading(), self.ticket_id);
+        let mut out = format!("{heading}\n{}\n", "=".repeat(heading.chars().count()));
+        for entry in &self.entries {
+            let when = entry
+                .created_at
+                .as_deref()
+                .map(|ts| format!("[{}] ", timestamp(ts)))
+                .unwrap_or_default();
+            let text = richtext::to_plain_text(&entry.content);
+            out.push('\n');
+            if entry.kind == EntryKind::Event {
+                out.push_str(&format!("{when}-- {text}\n"));
+                continue;
+            }
+
+            out.push_str(&format!("{when}{}", entry.author));
+            if let Some(label) = label(entry.kind) {
+                out.push_str(&format!(" ({label})"));
+            }
+            out.push_str(":\n");
+            for line in text.trim_end().lines() {
+                out.push_str("    ");
+                out.push_str(line);
+                out.push('\n');
+            }
+        }
+        out
+    }
+
+    /// A standalone HTML document.
+    pub fn to_html(&self) -> String {
+        let title = richtext::escape_html(&self.heading());
+        let mut out = format!(
+            "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>{title}</title>\n</head>\n<body>\n<h1>{title}</h1>\n<p>Ticket <code>{}</code></p>\n",
+            richtext::escape_html(&self.ticket_id)
+        );
+        for entry in &self.entries {
+            let kind = match entry.kind {
+                EntryKind::Customer => "customer",
+                EntryKind::Reply => "reply",
+                EntryKind::Note => "note",
+                EntryKind::Bot => "bot",
+                EntryKind::Event => "event",
+            };
+            let when = entry
+                .created_at
+                .as_deref()
+                .map(|ts| {
+                    format!(
+                        " <time datetime=\"{}\">{}</time>",
+                        richtext::escape_html(ts),
+                        timestamp(ts)
+                    )
+                })
+                .unwrap_or_default();
+
+            if entry.kind == EntryKind::Event {
+                out.push_str(&format!(
+                    "<p class=\"event\"><em>{}</em>{when}</p>\n",
+                    richtext::escape_html(&richtext::to_plain_text(&entry.content))
+                ));
+                continue;
+            }
+
+            let label = label(entry.kind)
+                .map(|label| format!(" ({label})"))
+                .unwrap_or_default();
+            out.push_str(&format!(
+                "<article class=\"{kind}\">\n<header><strong>{}</strong>{label}{when}</header>\n{}\n</article>\n",
+                richtext::escape_html(&entry.author),
+                richtext::to_html(&entry.content)
+            ));
+        }
+        out.push_str("</body>\n</html>\n");
+        out
+    }
+
+    fn heading(&self) -> String {
+        self.title
+            .clone()
+            .filter(|title| !title.is_empty())
+            .unwrap_or_else(|| format!("Ticket {}", self.ticket_id))
+    }
+}
+
+fn entry(message: &Message) -> Entry {
+    let kind = match message.message_type {
+        Some(MessageType::Note) => EntryKind::Note,
+        Some(MessageType::FeedbackUpdated) => EntryKind::Event,
+        Some(MessageType::Bot | MessageType::BotReply) => EntryKind::Bot,
+        _ if message.bot == Some(true) => EntryKind::Bot,
+        _ if message.is_team_reply() => EntryKind::Reply,
+        _ => EntryKind::Customer,
+    };
+
+    let author = message
+        .user
+        .as_ref()
+        .and_then(|u| u.display_name())
+        .or_else(|| message.session.as_ref().and_then(|s| s.display_name()))
+        .unwrap_or_else(|| match kind {
+            EntryKind::Bot => "Bot".to_string(),
+            EntryKind::Event => "System".to_string(),
+            EntryKind::Customer => "Customer".to_string(),
+            _ => "Team".to_string(),
+        });
+
+    Entry {
+        id: message.id.clone(),
+        kind,
+        author,
+        created_at: message.created_at.clone(),
+        content: message
+            .data
+            .as_ref()
+            .and_then(|d| d.content.clone())
+            .unwrap_or(serde_json::Value::Null),
+    }
+}
+
+fn label(kind: EntryKind) -> Option<&'static str> {
+    match kind {
+        EntryKind::Note => Some("internal note"),
+        EntryKind::Bot => Some("bot"),
+        _ => None,
+    }
+}
+
+fn timestamp(ts: &str) -> String {
+    DateTime::parse_from_rfc3339(ts)
+        .map(|dt| dt.to_utc().format("%Y-%m-%d %H:%M UTC").to_string())
+        .unwrap_or_else(|_| ts.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn fixture() -> (Ticket, Vec<Message>) {
+        let ticket: Ticket =
+            serde_json::from_value(json!({ "id": "t1", "title": "Login broken" })).unwrap();
+        let messages: Vec<Message> = serde_json::from_value(json!([
+            {
+                "id": "m3", "index": 3, "type": "FEEDBACK_UPDATED",
+                "createdAt": "2026-02-10T12:30:00.000Z",
+                "data": { "content": "Status changed to DONE" }
+            },
+            {
+                "id": "m1", "index": 1, "type": "USER_TEXT",
+                "createdAt": "2026-02-10T10:00:00.000Z",
+                "session": { "name": "Carl" },
+                "data": { "content": "Login is broken" }
+            },
+            {
+                "id": "m2", "index": 2, "type": "NOTE",
+                "createdAt": "2026-02-10T11:00:00.000Z",
+                "user": { "firstName": "Ann", "lastName": "Lee" },
+                "data": { "content": { "type": "doc", "content": [
+                    { "type": "paragraph", "content": [
+                        { "type": "text", "text": "Safari", "marks": [{ "type": "bold" }] },
+                        { "type": "text", "text": " only" }
+                    ]}
+                ]}}
+            }
+        ]))
+        .unwrap();
+        (ticket, messages)
+    }
+
+    #[test]
+    fn test_entries_are_chronological_and_classified() {
+        let (ticket, messages) = fixture();
+        let transcript = Transcript::new(&ticket, &messages, true);
+
+        let summary: Vec<_> = transcript
+            .entries
+            .iter()
+            .map(|e| (e.id.as_str(), e.kind, e.author.as_str()))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("m1", EntryKind::Customer, "Carl"),
+                ("m2", EntryKind::Note, "Ann Lee"),
+                ("m3", EntryKind::Event, "System"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_exclude_notes() {
+        let (ticket, messages) = fixture();
+        let transcript = Transcript::new(&ticket, &messages, false);
+        assert!(transcript.entries.iter().all(|e| e.kind != EntryKind::Note));
+        assert!(!transcript.to_text().contains("Safari"));
+    }
+
+    #[test]
+    fn test_markdown() {
+        let (ticket, messages) = fixture();
+        assert_eq!(
+            Transcript::new(&ticket, &messages, true).to_markdown(),
+            "# Login broken\n\nTicket `t1`\n\n\
+             ### Carl · 2026-02-10 10:00 UTC\n\nLogin is broken\n\n\
+             ### Ann Lee (internal note) · 2026-02-10 11:00 UTC\n\n**Safari** only\n\n\
+             _2026-02-10 12:30 UTC · Status changed to DONE_\n"
+        );
+    }
+
+    #[test]
+    fn test_markdown_escapes_names_and_title() {
+        let (mut ticket, mut messages) = fixture();
+        ticket.title = Some("<b>Login</b> *broken*".into());
+        messages[1].session =
+            serde_json::from_value(json!({ "name": "[Carl](https://evil)" })).ok();
+        let markdown = Transcript::new(&ticket, &messages, false).to_markdown();
+        assert!(markdown.starts_with("# \\<b\\>Login\\</b\\> \\*broken\\*\n"));
+        assert!(markdown.contains("### \\[Carl\\](https://evil) · "));
+    }
+
+    #[test]
+    fn test_text() {
+        let (ticket, messages) = fixture();
+        assert_eq!(
+            Transcript::new(&ticket, &messages, true).to_text(),
+            "Login broken (t1)\n=================\n\n\
+             [2026-02-10 10:00 UTC] Carl:\n    Login is broken\n\n\
+             [2026-02-10 11:00 UTC] Ann Lee (internal note):\n    Safari only\n\n\
+             [2026-02-10 12:30 UTC] -- Status changed to DONE\n"
+        );
+    }
+}